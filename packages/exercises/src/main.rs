@@ -156,7 +156,13 @@ fn kruskal_example() {
 
 fn prufer_to_tree_example() {
     let sequence = vec![4, 3, 1, 3, 1];
-    let tree = gtc::prufer_to_tree(&sequence);
+    let tree = match gtc::prufer_to_tree(&sequence) {
+        Ok(tree) => tree,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
 
     let undirected_graph: UndirectedGraph<GraphDefinition<usize>, Simple, usize> =
         UndirectedGraph::new(tree);
@@ -222,8 +228,7 @@ fn graph_distances_example() {
             ("g", "h", 1),
         ]);
 
-    let matrix = gtc::warshall_lightest_path_matrix(&graph);
-    let distances = gtc::compute_graph_distances(&matrix);
+    let distances = gtc::graph_distances(&graph);
     println!("Graph:\n{}", graph.to_latex());
     println!("Graph Distances:\n{}", distances.to_latex());
 }
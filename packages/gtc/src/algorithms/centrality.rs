@@ -0,0 +1,197 @@
+use crate::{AllPairsDistances, Graph, NodeId, hop_distances};
+
+/// One power-method step. Every node passes its score evenly to its successors, and the
+/// mass of dangling nodes (no successors) is spread uniformly over all nodes.
+fn pagerank_step<G>(graph: &G, nodes: &[NodeId], rank: &[f64], damping: f64) -> Vec<f64>
+where
+    G: Graph,
+{
+    let n = nodes.len() as f64;
+    let mut dangling = 0.0;
+    let mut incoming = vec![0.0; nodes.len()];
+
+    for (i, &u) in nodes.iter().enumerate() {
+        let successors: Vec<NodeId> = graph.successors(u).collect();
+        if successors.is_empty() {
+            dangling += rank[i];
+            continue;
+        }
+        let share = rank[i] / successors.len() as f64;
+        for v in successors {
+            incoming[v.0] += share;
+        }
+    }
+
+    incoming
+        .into_iter()
+        .map(|mass| (1.0 - damping) / n + damping * (mass + dangling / n))
+        .collect()
+}
+
+fn pagerank_run<G>(
+    graph: &G,
+    damping: f64,
+    max_iterations: usize,
+    epsilon: Option<f64>,
+) -> Vec<(G::Key, f64)>
+where
+    G: Graph,
+{
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    if nodes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut rank = vec![1.0 / nodes.len() as f64; nodes.len()];
+    for _ in 0..max_iterations {
+        let next = pagerank_step(graph, &nodes, &rank, damping);
+        let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if epsilon.is_some_and(|eps| delta < eps) {
+            break;
+        }
+    }
+
+    nodes
+        .into_iter()
+        .map(|id| (graph.node_key(id).clone(), rank[id.0]))
+        .collect()
+}
+
+/// PageRank scores after a fixed number of power-method iterations, starting from the
+/// uniform distribution. `damping` is the probability of following an edge (usually 0.85).
+/// Dangling nodes redistribute their score uniformly, so the scores always sum to 1.
+/// Undirected edges are followed in both directions.
+pub fn pagerank<G>(graph: &G, damping: f64, iterations: usize) -> Vec<(G::Key, f64)>
+where
+    G: Graph,
+{
+    pagerank_run(graph, damping, iterations, None)
+}
+
+/// PageRank iterated until the L1 distance between consecutive score vectors drops below
+/// `epsilon`, or `max_iterations` is reached.
+pub fn pagerank_converged<G>(
+    graph: &G,
+    damping: f64,
+    epsilon: f64,
+    max_iterations: usize,
+) -> Vec<(G::Key, f64)>
+where
+    G: Graph,
+{
+    pagerank_run(graph, damping, max_iterations, Some(epsilon))
+}
+
+/// Closeness of every node from a distance matrix, with the Wasserman–Faust normalization
+/// for disconnected graphs: `(r - 1) / sum` scaled by `(r - 1) / (n - 1)`, where `r` counts
+/// the nodes reachable from it (itself included) and `sum` is the total distance to them.
+fn closeness_from<K, W>(
+    distances: &AllPairsDistances<K, W>,
+    to_f64: impl Fn(W) -> f64,
+) -> Vec<(K, f64)>
+where
+    K: Clone,
+    W: Copy,
+{
+    let n = distances.nodes.len();
+
+    distances
+        .nodes
+        .iter()
+        .zip(&distances.distances)
+        .enumerate()
+        .map(|(i, (key, row))| {
+            let reached: Vec<f64> = row
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .filter_map(|(_, d)| d.map(&to_f64))
+                .collect();
+            let total: f64 = reached.iter().sum();
+
+            let closeness = if reached.is_empty() || total <= 0.0 {
+                0.0
+            } else {
+                let others = reached.len() as f64;
+                (others / total) * (others / (n - 1) as f64)
+            };
+            (key.clone(), closeness)
+        })
+        .collect()
+}
+
+/// Closeness centrality over hop counts, following edge directions. Nodes that reach nobody
+/// get 0; see `weighted_closeness_centrality` for the normalization used.
+///
+/// Edge weights are ignored, so every edge counts as distance 1. For weighted graphs use
+/// `weighted_closeness_centrality` on a distance matrix such as the result of `johnson`.
+pub fn closeness_centrality<G>(graph: &G) -> Vec<(G::Key, f64)>
+where
+    G: Graph,
+{
+    closeness_from(&hop_distances(graph), |d| d as f64)
+}
+
+/// Closeness centrality from a weighted distance matrix, e.g. the result of `johnson` or one
+/// converted from `warshall_lightest_path_matrix`. A node reaching `r - 1` others at total
+/// distance `sum` scores `(r - 1) / sum`, scaled by `(r - 1) / (n - 1)` (Wasserman–Faust) so
+/// nodes in small components are not favoured. Nodes that reach nobody get 0.
+pub fn weighted_closeness_centrality<K, W>(distances: &AllPairsDistances<K, W>) -> Vec<(K, f64)>
+where
+    K: Clone,
+    W: Copy + Into<f64>,
+{
+    closeness_from(distances, Into::into)
+}
+
+/// Degree of every node divided by `n - 1`, the largest degree possible in a simple graph.
+/// In directed graphs both in- and out-degree count. Graphs with fewer than two nodes get 0.
+pub fn degree_centrality<G>(graph: &G) -> Vec<(G::Key, f64)>
+where
+    G: Graph,
+{
+    let n = graph.order();
+
+    graph
+        .node_ids()
+        .map(|id| {
+            let centrality = if n < 2 {
+                0.0
+            } else {
+                graph.degree(id) as f64 / (n - 1) as f64
+            };
+            (graph.node_key(id).clone(), centrality)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DirectedGraph, GraphDefinition, Simple};
+
+    type Directed = DirectedGraph<GraphDefinition<String>, Simple, String>;
+
+    fn assert_uniform(scores: &[(String, f64)]) {
+        for (_, score) in scores {
+            assert!((score - 1.0 / 3.0).abs() < 1e-9, "{scores:?}");
+        }
+    }
+
+    #[test]
+    fn pagerank_of_a_cycle_is_uniform() {
+        let cycle = Directed::from_edges([("a", "b"), ("b", "c"), ("c", "a")]);
+        assert_uniform(&pagerank(&cycle, 0.85, 20));
+        assert_uniform(&pagerank_converged(&cycle, 0.85, 1e-12, 100));
+    }
+
+    #[test]
+    fn dangling_nodes_keep_the_total() {
+        let star = Directed::from_edges([("a", "b"), ("a", "c")]);
+        let scores = pagerank_converged(&star, 0.85, 1e-12, 100);
+        let total: f64 = scores.iter().map(|(_, s)| s).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(scores[1].1 > scores[0].1);
+    }
+}
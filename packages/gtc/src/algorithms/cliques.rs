@@ -0,0 +1,90 @@
+use crate::{Graph, NodeId, colorings::WorkingGraph};
+
+/// Bron–Kerbosch recursion with pivoting. `r` is the clique being grown, `p` the candidates
+/// that extend it and `x` the vertices already excluded; `r` is maximal once both are empty.
+fn bron_kerbosch(
+    wg: &WorkingGraph,
+    r: &mut Vec<usize>,
+    p: Vec<usize>,
+    mut x: Vec<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if p.is_empty() && x.is_empty() {
+        cliques.push(r.clone());
+        return;
+    }
+
+    // Pivot on the vertex with the most neighbors in P to minimize branching.
+    let pivot = p
+        .iter()
+        .chain(x.iter())
+        .copied()
+        .max_by_key(|&u| p.iter().filter(|&&v| wg.adj[u][v]).count())
+        .expect("P or X is non-empty");
+
+    let candidates: Vec<usize> = p.iter().copied().filter(|&v| !wg.adj[pivot][v]).collect();
+    let mut p = p;
+
+    for v in candidates {
+        let next_p = p.iter().copied().filter(|&u| wg.adj[v][u]).collect();
+        let next_x = x.iter().copied().filter(|&u| wg.adj[v][u]).collect();
+
+        r.push(v);
+        bron_kerbosch(wg, r, next_p, next_x, cliques);
+        r.pop();
+
+        p.retain(|&u| u != v);
+        x.push(v);
+    }
+}
+
+/// Enumerates all maximal cliques of the underlying simple undirected graph
+/// using the Bron–Kerbosch algorithm with pivoting.
+pub fn maximal_cliques<G>(graph: &G) -> Vec<Vec<G::Key>>
+where
+    G: Graph,
+{
+    if graph.order() == 0 {
+        return Vec::new();
+    }
+
+    let wg = WorkingGraph::from_graph(graph);
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+
+    let mut cliques = Vec::new();
+    bron_kerbosch(
+        &wg,
+        &mut Vec::new(),
+        (0..wg.n).collect(),
+        Vec::new(),
+        &mut cliques,
+    );
+
+    cliques
+        .into_iter()
+        .map(|mut clique| {
+            clique.sort_unstable();
+            clique
+                .into_iter()
+                .map(|i| graph.node_key(nodes[i]).clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns a maximum clique, i.e. a largest maximal clique.
+/// Its size is a lower bound for the chromatic number.
+pub fn max_clique<G>(graph: &G) -> Vec<G::Key>
+where
+    G: Graph,
+{
+    maximal_cliques(graph)
+        .into_iter()
+        .fold(Vec::new(), |best, clique| {
+            if clique.len() > best.len() {
+                clique
+            } else {
+                best
+            }
+        })
+}
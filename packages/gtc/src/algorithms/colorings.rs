@@ -1,7 +1,11 @@
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::ops::{Add, Mul, Sub};
 
-use crate::{Graph, LatexDisplay, NodeId};
+use crate::{
+    Graph, LatexDisplay, LatexVisualDisplay, NodeId, VisualEdge, VisualGraphData,
+    generate_latex_graph,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Polynomial {
@@ -30,6 +34,9 @@ impl Polynomial {
     }
 
     /// Evaluates the polynomial at a given value x.
+    ///
+    /// Overflows for large values (e.g. counting colorings of graphs with ~20 nodes);
+    /// use `eval_checked` or `eval_big` when that is a concern.
     pub fn eval(&self, x: i64) -> i64 {
         let mut result = 0;
         let mut power_of_x = 1;
@@ -40,12 +47,154 @@ impl Polynomial {
         result
     }
 
+    /// Evaluates the polynomial at x using Horner's scheme, returning `None` on `i64` overflow.
+    pub fn eval_checked(&self, x: i64) -> Option<i64> {
+        self.coeffs
+            .iter()
+            .rev()
+            .try_fold(0i64, |acc, &c| acc.checked_mul(x)?.checked_add(c))
+    }
+
+    /// Evaluates the polynomial at x in `i128`, returning `None` on overflow.
+    pub fn eval_big(&self, x: i128) -> Option<i128> {
+        self.coeffs
+            .iter()
+            .rev()
+            .try_fold(0i128, |acc, &c| acc.checked_mul(x)?.checked_add(c as i128))
+    }
+
     /// Normalizes vector (removes trailing zeros).
     fn normalize(&mut self) {
         while self.coeffs.len() > 1 && self.coeffs.last() == Some(&0) {
             self.coeffs.pop();
         }
     }
+
+    /// Degree of the polynomial, or `None` for the zero polynomial.
+    fn degree(&self) -> Option<usize> {
+        self.coeffs.iter().rposition(|&c| c != 0)
+    }
+
+    /// Exact division: if `self` divides `other` over the integers, returns `other / self`.
+    /// Returns `None` when the division leaves a remainder or would need fractional coefficients.
+    pub fn divides(&self, other: &Polynomial) -> Option<Polynomial> {
+        let d = self.degree()?;
+        let lead = self.coeffs[d];
+
+        let Some(other_degree) = other.degree() else {
+            return Some(Polynomial::zero());
+        };
+        if other_degree < d {
+            return None;
+        }
+
+        let mut remainder = other.coeffs[..=other_degree].to_vec();
+        let mut quotient = vec![0; other_degree - d + 1];
+        for i in (0..quotient.len()).rev() {
+            let c = remainder[i + d];
+            if c % lead != 0 {
+                return None;
+            }
+            let factor = c / lead;
+            quotient[i] = factor;
+            for (j, &dc) in self.coeffs[..=d].iter().enumerate() {
+                remainder[i + j] -= factor * dc;
+            }
+        }
+
+        if remainder.iter().any(|&c| c != 0) {
+            return None;
+        }
+
+        let mut p = Polynomial { coeffs: quotient };
+        p.normalize();
+        Some(p)
+    }
+
+    /// Divides by `(x - root)` using synthetic division, if it is a factor.
+    fn divide_by_root(&self, root: i64) -> Option<Polynomial> {
+        let d = self.degree()?;
+        if d == 0 {
+            return None;
+        }
+
+        let mut quotient = vec![0; d];
+        let mut carry = 0;
+        for i in (0..d).rev() {
+            carry = self.coeffs[i + 1] + carry * root;
+            quotient[i] = carry;
+        }
+        if self.coeffs[0] + carry * root != 0 {
+            return None;
+        }
+
+        Some(Polynomial { coeffs: quotient })
+    }
+
+    /// Splits off the roots in `0..=n`, where `n` is the degree, returning them (with
+    /// multiplicity, ascending) together with the remaining factor. Integer roots of a
+    /// chromatic polynomial are always in that range, so for those the remaining factor has
+    /// no integer roots; for other polynomials, roots outside it stay in the remainder.
+    fn split_linear(&self) -> (Vec<i64>, Polynomial) {
+        let mut roots = Vec::new();
+        let mut rest = self.clone();
+        rest.normalize();
+        let n = rest.degree().unwrap_or(0) as i64;
+
+        for root in 0..=n {
+            while let Some(quotient) = rest.divide_by_root(root) {
+                roots.push(root);
+                rest = quotient;
+            }
+        }
+
+        (roots, rest)
+    }
+
+    /// Integer roots in `0..=n` of the polynomial of degree `n`, with multiplicity, in
+    /// ascending order, found by repeated synthetic division.
+    pub fn factor_linear(&self) -> Vec<i64> {
+        self.split_linear().0
+    }
+
+    /// Renders the polynomial as a product of linear factors, e.g. `x(x-1)(x-2)`.
+    /// Only roots in `0..=n` are factored out; the remaining factor is left unfactored and
+    /// printed expanded in parentheses, e.g. `x(x-1)(x^{2} - 3x + 3)` for the 4-cycle.
+    pub fn to_latex_factored(&self) -> String {
+        let (roots, rest) = self.split_linear();
+        if roots.is_empty() {
+            return self.to_latex();
+        }
+
+        let mut factors = String::new();
+        match rest.degree() {
+            Some(0) if rest.coeffs[0] == -1 => factors.push('-'),
+            Some(0) if rest.coeffs[0] != 1 => factors.push_str(&rest.coeffs[0].to_string()),
+            _ => {}
+        }
+
+        let mut i = 0;
+        while i < roots.len() {
+            let root = roots[i];
+            let multiplicity = roots[i..].iter().take_while(|&&r| r == root).count();
+            let factor = match root.cmp(&0) {
+                std::cmp::Ordering::Equal => "x".to_string(),
+                std::cmp::Ordering::Greater => format!("(x-{})", root),
+                std::cmp::Ordering::Less => format!("(x+{})", -root),
+            };
+            factors.push_str(&factor);
+            if multiplicity > 1 {
+                factors.push_str(&format!("^{{{}}}", multiplicity));
+            }
+            i += multiplicity;
+        }
+
+        if rest.degree().is_some_and(|d| d > 0) {
+            factors.push_str(&format!("({})", rest));
+        }
+
+        format!("P_G(x) = {}", factors)
+    }
 }
 
 impl Add for Polynomial {
@@ -159,14 +308,16 @@ impl LatexDisplay for Polynomial {
     }
 }
 
+/// Simple undirected graph as a dense boolean adjacency matrix, indexed by node position.
+/// Self-loops are dropped and parallel edges collapse into one.
 #[derive(Clone, Debug)]
-struct WorkingGraph {
-    adj: Vec<Vec<bool>>,
-    n: usize,
+pub(crate) struct WorkingGraph {
+    pub(crate) adj: Vec<Vec<bool>>,
+    pub(crate) n: usize,
 }
 
 impl WorkingGraph {
-    fn from_graph<G>(graph: &G) -> Self
+    pub(crate) fn from_graph<G>(graph: &G) -> Self
     where
         G: Graph,
     {
@@ -234,6 +385,44 @@ impl WorkingGraph {
         self.adj[v][u] = true;
     }
 
+    /// Key identifying the graph up to isomorphism, used to memoize chromatic polynomials.
+    ///
+    /// Vertices are relabeled by (degree, sorted neighbor degrees), ties kept in index order,
+    /// and the relabeled adjacency rows are packed into bitsets. Equal keys always describe
+    /// isomorphic graphs, and structurally identical subproblems usually map to the same key.
+    fn canonical_key(&self) -> Vec<u64> {
+        let degrees: Vec<usize> = self
+            .adj
+            .iter()
+            .map(|row| row.iter().filter(|&&b| b).count())
+            .collect();
+        let signature = |v: usize| {
+            let mut neighbor_degrees: Vec<usize> = (0..self.n)
+                .filter(|&u| self.adj[v][u])
+                .map(|u| degrees[u])
+                .collect();
+            neighbor_degrees.sort_unstable();
+            (degrees[v], neighbor_degrees)
+        };
+
+        let mut order: Vec<usize> = (0..self.n).collect();
+        order.sort_by_cached_key(|&v| signature(v));
+
+        let words = self.n.div_ceil(64);
+        let mut key = Vec::with_capacity(1 + self.n * words);
+        key.push(self.n as u64);
+        for &v in &order {
+            let mut row = vec![0u64; words];
+            for (j, &u) in order.iter().enumerate() {
+                if self.adj[v][u] {
+                    row[j / 64] |= 1 << (j % 64);
+                }
+            }
+            key.extend(row);
+        }
+        key
+    }
+
     /// Contract edge (u, v). Merges v into u.
     /// Removes vertex v.
     fn contract(&self, u: usize, v: usize) -> Self {
@@ -322,18 +511,44 @@ where
         m => m,
     };
 
+    let mut cache = PolynomialCache::default();
     match resolved_method {
-        ChromaticPolynomialMethod::RemoveEdges => compute_poly_remove(wg),
-        ChromaticPolynomialMethod::AddEdges => compute_poly_add(wg),
+        ChromaticPolynomialMethod::RemoveEdges => compute_poly_remove(wg, &mut cache),
+        ChromaticPolynomialMethod::AddEdges => compute_poly_add(wg, &mut cache),
         _ => unreachable!(),
     }
 }
 
+/// Chromatic polynomials of already solved subproblems, keyed by `WorkingGraph::canonical_key`.
+#[derive(Default)]
+struct PolynomialCache {
+    polys: HashMap<Vec<u64>, Polynomial>,
+    /// Subproblems answered from `polys` instead of recursing.
+    hits: usize,
+}
+
+impl PolynomialCache {
+    fn get(&mut self, key: &[u64]) -> Option<Polynomial> {
+        let poly = self.polys.get(key).cloned();
+        self.hits += usize::from(poly.is_some());
+        poly
+    }
+
+    fn insert(&mut self, key: Vec<u64>, poly: Polynomial) {
+        self.polys.insert(key, poly);
+    }
+}
+
 /// Recursive implementation for P(G) = P(G-e) - P(G/e)
-fn compute_poly_remove(g: WorkingGraph) -> Polynomial {
+fn compute_poly_remove(g: WorkingGraph, cache: &mut PolynomialCache) -> Polynomial {
     // Base case: Empty graph (no edges)
     // P(E_n) = x^n
     if let Some((u, v)) = g.find_edge() {
+        let key = g.canonical_key();
+        if let Some(poly) = cache.get(&key) {
+            return poly;
+        }
+
         // G_minus: G with edge removed
         let mut g_minus = g.clone();
         g_minus.remove_edge(u, v);
@@ -342,7 +557,9 @@ fn compute_poly_remove(g: WorkingGraph) -> Polynomial {
         let g_contract = g.contract(u, v);
 
         // P(G) = P(G-e) - P(G/e)
-        compute_poly_remove(g_minus) - compute_poly_remove(g_contract)
+        let poly = compute_poly_remove(g_minus, cache) - compute_poly_remove(g_contract, cache);
+        cache.insert(key, poly.clone());
+        poly
     } else {
         // No edges, return x^n
         Polynomial::from_monomial(g.n, 1)
@@ -350,10 +567,15 @@ fn compute_poly_remove(g: WorkingGraph) -> Polynomial {
 }
 
 /// Recursive implementation for P(G) = P(G+e) + P(G/e)
-fn compute_poly_add(g: WorkingGraph) -> Polynomial {
+fn compute_poly_add(g: WorkingGraph, cache: &mut PolynomialCache) -> Polynomial {
     // Base case: Complete graph
     // P(K_n) = x(x-1)...(x-n+1)
     if let Some((u, v)) = g.find_non_edge() {
+        let key = g.canonical_key();
+        if let Some(poly) = cache.get(&key) {
+            return poly;
+        }
+
         // G_plus: G with edge added
         let mut g_plus = g.clone();
         g_plus.add_edge(u, v);
@@ -362,7 +584,9 @@ fn compute_poly_add(g: WorkingGraph) -> Polynomial {
         let g_contract = g.contract(u, v);
 
         // P(G) = P(G+e) + P(G/e)
-        compute_poly_add(g_plus) + compute_poly_add(g_contract)
+        let poly = compute_poly_add(g_plus, cache) + compute_poly_add(g_contract, cache);
+        cache.insert(key, poly.clone());
+        poly
     } else {
         // Complete graph K_n
         // Result is x(x-1)...(x-n+1)
@@ -378,12 +602,17 @@ fn compute_poly_add(g: WorkingGraph) -> Polynomial {
     }
 }
 
+/// Number of proper colorings of the graph using at most `k` colors.
+///
+/// Panics if the count does not fit in an `i64`; evaluate the chromatic polynomial
+/// with `Polynomial::eval_big` for larger graphs or color counts.
 pub fn num_k_colorings<G>(graph: &G, k: i64) -> i64
 where
     G: Graph,
 {
     let poly = chromatic_polynomial(graph, ChromaticPolynomialMethod::Auto);
-    poly.eval(k)
+    poly.eval_checked(k)
+        .expect("Number of colorings overflows i64, use Polynomial::eval_big instead")
 }
 
 pub fn chromatic_number<G>(graph: &G) -> usize
@@ -394,10 +623,172 @@ where
     let n = graph.order();
 
     for k in 1..=n {
-        if poly.eval(k as i64) > 0 {
+        let colorings = poly
+            .eval_big(k as i128)
+            .expect("Number of colorings overflows i128");
+        if colorings > 0 {
             return k;
         }
     }
 
     if n == 0 { 0 } else { 1 }
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColoringOrder {
+    /// Color nodes in the order of their ids.
+    NodeIdOrder,
+    /// Welsh–Powell: color nodes by decreasing degree (ties broken by id).
+    LargestDegreeFirst,
+}
+
+/// Greedy coloring: visits nodes in the given order and assigns each one the smallest
+/// color index not used by an already colored neighbor. Returns the color of every node,
+/// in node id order. Self-loops are ignored.
+pub fn greedy_coloring<G>(graph: &G, order: ColoringOrder) -> Vec<(G::Key, usize)>
+where
+    G: Graph,
+{
+    let mut nodes: Vec<NodeId> = graph.node_ids().collect();
+    if order == ColoringOrder::LargestDegreeFirst {
+        nodes.sort_by_key(|&v| std::cmp::Reverse(graph.degree(v)));
+    }
+
+    let mut colors: Vec<Option<usize>> = vec![None; graph.order()];
+    for v in nodes {
+        let used: BTreeSet<usize> = graph
+            .neighborhood(v)
+            .filter(|&u| u != v)
+            .filter_map(|u| colors[u.0])
+            .collect();
+        let color = (0..)
+            .find(|c| !used.contains(c))
+            .expect("Colors are unbounded");
+        colors[v.0] = Some(color);
+    }
+
+    graph
+        .node_ids()
+        .map(|v| {
+            let color = colors[v.0].expect("Every node is colored");
+            (graph.node_key(v).clone(), color)
+        })
+        .collect()
+}
+
+/// TikZ fill colors used for the color classes of a coloring, cycled when exhausted.
+const COLOR_FILLS: [&str; 8] = [
+    "red!30",
+    "blue!30",
+    "green!30",
+    "yellow!50",
+    "orange!40",
+    "violet!30",
+    "cyan!30",
+    "brown!30",
+];
+
+/// A graph together with a node coloring, rendered with one fill color per color class.
+pub struct ColoredGraph<'a, G>
+where
+    G: Graph,
+{
+    pub graph: &'a G,
+    pub coloring: &'a [(G::Key, usize)],
+}
+
+impl<G> LatexVisualDisplay for ColoredGraph<'_, G>
+where
+    G: Graph,
+    G::Key: Display,
+{
+    fn to_latex_visual(&self) -> String {
+        let labels: Vec<String> = self
+            .graph
+            .node_ids()
+            .map(|v| self.graph.node_key(v).to_string())
+            .collect();
+
+        let mut pairs = BTreeSet::new();
+        for eid in self.graph.edge_ids() {
+            let (u, v) = self.graph.endpoints(eid);
+            pairs.insert((u.0.min(v.0), u.0.max(v.0)));
+        }
+        let edges = pairs
+            .into_iter()
+            .map(|(u, v)| VisualEdge { u, v, label: None })
+            .collect();
+
+        let node_styles = self
+            .graph
+            .node_ids()
+            .map(|v| {
+                let key = self.graph.node_key(v);
+                self.coloring
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, color)| format!("fill={}", COLOR_FILLS[color % COLOR_FILLS.len()]))
+            })
+            .collect();
+
+        generate_latex_graph(
+            VisualGraphData::new(labels, edges, false).with_node_styles(node_styles),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erdos_renyi;
+
+    #[test]
+    fn factors_roots_up_to_the_degree() {
+        // x(x - 1)(x - 2)^2, the chromatic polynomial of a triangle with a pendant node
+        let p = Polynomial {
+            coeffs: vec![0, -4, 8, -5, 1],
+        };
+        assert_eq!(p.factor_linear(), vec![0, 1, 2, 2]);
+        assert_eq!(p.to_latex_factored(), "P_G(x) = x(x-1)(x-2)^{2}");
+
+        // x(x - 1)(x^2 - 3x + 3), the 4-cycle: the quadratic has no integer roots
+        let c4 = Polynomial {
+            coeffs: vec![0, -3, 6, -4, 1],
+        };
+        assert_eq!(c4.factor_linear(), vec![0, 1]);
+        assert_eq!(c4.to_latex_factored(), "P_G(x) = x(x-1)(x^{2} - 3x + 3)");
+
+        // x(x + 1) has a negative root, which is outside 0..=n and stays unfactored
+        let negative = Polynomial {
+            coeffs: vec![0, 1, 1],
+        };
+        assert_eq!(negative.factor_linear(), vec![0]);
+    }
+
+    #[test]
+    fn memo_cuts_the_recursion() {
+        let k8 = erdos_renyi(8, 1.0, 0);
+        let mut cache = PolynomialCache::default();
+        let poly = compute_poly_remove(WorkingGraph::from_graph(&k8), &mut cache);
+        assert_eq!(poly.factor_linear(), (0..8).collect::<Vec<i64>>());
+        // Without the memo the recursion branches in two at each of the 28 edges
+        assert!(cache.hits > 0 && cache.polys.len() < 100);
+
+        // Outer 5-cycle, spokes and the inner pentagram
+        let mut petersen = erdos_renyi(10, 0.0, 0);
+        for i in 0..5 {
+            for (u, v) in [(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)] {
+                petersen
+                    .add_edge_checked(NodeId(u), NodeId(v), (), Some(()))
+                    .unwrap();
+            }
+        }
+        let mut cache = PolynomialCache::default();
+        let poly = compute_poly_remove(WorkingGraph::from_graph(&petersen), &mut cache);
+        assert!(cache.hits > 0 && cache.polys.len() < 400);
+        assert_eq!(
+            poly,
+            chromatic_polynomial(&petersen, ChromaticPolynomialMethod::AddEdges)
+        );
+    }
+}
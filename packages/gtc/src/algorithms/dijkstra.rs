@@ -1,8 +1,13 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Display};
 use std::hash::{Hash, RandomState};
 
 use indexmap::IndexSet;
 
-use crate::{EdgeWeights, Graph, LatexDisplay, NodeId, StorageRepresentation};
+use crate::{
+    EdgeId, EdgeWeights, Graph, GraphDefinition, LatexDisplay, NodeId, StorageRepresentation,
+    WeightLabel, generate_latex_graph, latex::graph_visual_data,
+};
 
 pub struct DijkstraResult<K>
 where
@@ -39,9 +44,87 @@ where
 
         Some((tentative_weight, path))
     }
+
+    /// Edges of the lightest path to `target`, in order from the start node. Among parallel
+    /// edges the lightest one is picked, as Dijkstra relaxed it. `graph` must be the graph
+    /// Dijkstra ran on.
+    pub fn lightest_path_edges_to<G>(&self, graph: &G, target: &K) -> Option<Vec<EdgeId>>
+    where
+        G: Graph + EdgeWeights<W = i32>,
+    {
+        let target_index = self
+            .nodes
+            .iter()
+            .position(|k| k == target)
+            .expect("Target node not found in DijkstraResult");
+
+        self.tentative_weights[target_index]?;
+
+        let mut edges = Vec::new();
+        let mut current = NodeId(target_index);
+
+        while let Some(pred) = self.predecessors[current.0] {
+            let edge = graph
+                .edges_between(pred, current)
+                .filter(|&e| graph.weight_of(e).is_some())
+                .min_by_key(|&e| graph.weight_of(e))
+                .expect("There should be a weighted edge between consecutive path nodes");
+            edges.push(edge);
+            current = pred;
+        }
+        edges.reverse();
+
+        Some(edges)
+    }
+
+    /// The shortest-path tree as a graph, with one arc from each predecessor, weighted by
+    /// the edge Dijkstra relaxed. Unreachable nodes are kept as isolated vertices.
+    pub fn shortest_path_tree(&self) -> GraphDefinition<K, (), (), i32>
+    where
+        K: Debug,
+    {
+        let mut tree = GraphDefinition::new();
+        for key in &self.nodes {
+            tree.add_node(key.clone(), ());
+        }
+
+        for (v, pred) in self.predecessors.iter().enumerate() {
+            let Some(u) = *pred else {
+                continue;
+            };
+            let weight = self.tentative_weights[v]
+                .zip(self.tentative_weights[u.0])
+                .map(|(to, from)| to - from);
+            tree.add_edge_by_id(u, NodeId(v), (), weight);
+        }
+
+        tree
+    }
+
+    /// Draws `graph` with the shortest-path tree found by this run highlighted.
+    /// `graph` must be the graph Dijkstra ran on.
+    pub fn to_latex_visual_on<G>(&self, graph: &G) -> String
+    where
+        G: Graph + EdgeWeights,
+        G::Key: Display,
+        G::W: WeightLabel,
+    {
+        let mut data = graph_visual_data(graph);
+        data.highlight_edges = self
+            .predecessors
+            .iter()
+            .enumerate()
+            .filter_map(|(v, pred)| pred.map(|u| (u.0, v)))
+            .collect();
+
+        generate_latex_graph(data)
+    }
 }
 
-impl LatexDisplay for DijkstraResult<String> {
+impl<K> LatexDisplay for DijkstraResult<K>
+where
+    K: Clone + Eq + Hash + Display,
+{
     fn to_latex(&self) -> String {
         let mut result = String::new();
         result.push_str("\\begin{tabular}{|c|c|c|}\n\\hline\n");
@@ -69,6 +152,24 @@ impl LatexDisplay for DijkstraResult<String> {
 }
 
 pub fn dijkstra<G, S, K>(graph: &G, start: K) -> DijkstraResult<K>
+where
+    G: Graph<Storage = S> + EdgeWeights<W = i32>,
+    S: StorageRepresentation<Key = K, Weight = i32>,
+    K: Clone + Eq + Hash,
+{
+    dijkstra_with(graph, start, &HashSet::new(), &HashSet::new())
+}
+
+/// Dijkstra as if `blocked_nodes` and `blocked_edges` were removed from the graph, e.g. to
+/// find the shortest paths when a link fails. Blocking the start node leaves every node
+/// unreachable, and a blocked target is always unreachable. Undirected graphs may store
+/// each direction of an edge under its own id; block both to remove the edge.
+pub fn dijkstra_with<G, S, K>(
+    graph: &G,
+    start: K,
+    blocked_nodes: &HashSet<NodeId>,
+    blocked_edges: &HashSet<EdgeId>,
+) -> DijkstraResult<K>
 where
     G: Graph<Storage = S> + EdgeWeights<W = i32>,
     S: StorageRepresentation<Key = K, Weight = i32>,
@@ -86,11 +187,16 @@ where
         predecessors.push(None);
     }
 
-    tentative_weights[source_id.0] = Some(0);
+    if !blocked_nodes.contains(&source_id) {
+        tentative_weights[source_id.0] = Some(0);
+    }
     predecessors[source_id.0] = None;
 
-    let mut unvisited: IndexSet<NodeId, RandomState> =
-        IndexSet::from_iter((0..graph.order()).map(|i| NodeId(i)));
+    let mut unvisited: IndexSet<NodeId, RandomState> = IndexSet::from_iter(
+        (0..graph.order())
+            .map(|i| NodeId(i))
+            .filter(|v| !blocked_nodes.contains(v)),
+    );
 
     while !unvisited.is_empty() {
         let current = unvisited
@@ -108,6 +214,11 @@ where
             .cloned()
             .expect("No reachable unvisited nodes remaining");
 
+        // Only unreachable nodes are left
+        let Some(current_weight) = tentative_weights[current.0] else {
+            break;
+        };
+
         unvisited.shift_remove(&current);
 
         for neighbor in graph.successors(current) {
@@ -115,14 +226,16 @@ where
                 continue;
             }
 
-            let edges = graph.edges_between(current, neighbor);
-            let min_edge_weight = edges.filter_map(|eid| graph.weight_of(eid)).min().expect(
-                "There should be at least one edge between current and neighbor in successors",
-            );
+            let Some(min_edge_weight) = graph
+                .edges_between(current, neighbor)
+                .filter(|eid| !blocked_edges.contains(eid))
+                .filter_map(|eid| graph.weight_of(eid))
+                .min()
+            else {
+                continue;
+            };
 
-            let alt_weight = tentative_weights[current.0]
-                .map(|w| w + min_edge_weight)
-                .expect("Current node should have a tentative weight");
+            let alt_weight = current_weight + min_edge_weight;
 
             if tentative_weights[neighbor.0].map_or(true, |w| alt_weight < w) {
                 tentative_weights[neighbor.0] = Some(alt_weight);
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::Sub;
+
+use crate::{
+    FlowNetwork, GraphBase, GraphKindMarker, MutableStorage, StorageRepresentation, Weight,
+};
+
+/// Residual arc used by Dinic's algorithm. Every network edge is stored together
+/// with its reverse arc; `rev` is the index of the twin arc in the adjacency list of `to`.
+struct ResidualArc<C> {
+    to: usize,
+    capacity: C,
+    rev: usize,
+}
+
+struct DinicState<C> {
+    adj: Vec<Vec<ResidualArc<C>>>,
+    level: Vec<Option<usize>>,
+    next_arc: Vec<usize>,
+}
+
+impl<C> DinicState<C>
+where
+    C: Weight + Sub<Output = C>,
+{
+    fn new(n: usize) -> Self {
+        Self {
+            adj: (0..n).map(|_| Vec::new()).collect(),
+            level: vec![None; n],
+            next_arc: vec![0; n],
+        }
+    }
+
+    fn add_arc(&mut self, from: usize, to: usize, capacity: C) {
+        let rev_from = self.adj[to].len() + usize::from(from == to);
+        let rev_to = self.adj[from].len();
+        self.adj[from].push(ResidualArc {
+            to,
+            capacity,
+            rev: rev_from,
+        });
+        self.adj[to].push(ResidualArc {
+            to: from,
+            capacity: C::zero(),
+            rev: rev_to,
+        });
+    }
+
+    /// Builds the level graph with a BFS from the source. Returns whether the sink is reachable.
+    fn build_levels(&mut self, source: usize, sink: usize) -> bool {
+        self.level.iter_mut().for_each(|l| *l = None);
+        self.level[source] = Some(0);
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            let next_level = self.level[u].map(|l| l + 1);
+            for arc in &self.adj[u] {
+                if arc.capacity > C::zero() && self.level[arc.to].is_none() {
+                    self.level[arc.to] = next_level;
+                    queue.push_back(arc.to);
+                }
+            }
+        }
+
+        self.level[sink].is_some()
+    }
+
+    /// Pushes flow along a single augmenting path of the level graph, advancing the
+    /// per-node arc iterators past saturated or dead-end arcs. A `limit` of `None` is
+    /// unbounded, and the sink is never reached with one.
+    fn push(&mut self, u: usize, sink: usize, limit: Option<C>) -> C {
+        if u == sink {
+            return limit.expect("The source is not the sink");
+        }
+
+        while self.next_arc[u] < self.adj[u].len() {
+            let i = self.next_arc[u];
+            let (to, capacity) = (self.adj[u][i].to, self.adj[u][i].capacity);
+
+            let is_level_arc = match (self.level[u], self.level[to]) {
+                (Some(lu), Some(lv)) => lv == lu + 1,
+                _ => false,
+            };
+
+            if capacity > C::zero() && is_level_arc {
+                let limit = match limit {
+                    Some(limit) if limit < capacity => limit,
+                    _ => capacity,
+                };
+                let pushed = self.push(to, sink, Some(limit));
+                if pushed > C::zero() {
+                    let rev = self.adj[u][i].rev;
+                    self.adj[u][i].capacity = self.adj[u][i].capacity - pushed;
+                    self.adj[to][rev].capacity = self.adj[to][rev].capacity + pushed;
+                    return pushed;
+                }
+            }
+
+            self.next_arc[u] += 1;
+        }
+
+        C::zero()
+    }
+}
+
+/// Computes the maximum flow value of a network using Dinic's algorithm.
+///
+/// Each phase builds a BFS level graph and then saturates it with a blocking flow,
+/// giving O(V^2 E) overall. The initial flow of the network is ignored: the maximum
+/// flow value does not depend on it, so the result matches `ford_fulkerson`.
+pub fn dinic<S, GK, K, D, E, W, C>(network: FlowNetwork<S, GK, K, D, E, W, C>) -> C
+where
+    S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + Clone,
+    GK: GraphKindMarker + Clone,
+    K: Debug + Clone + Eq + Hash + Display,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+    C: Weight + Sub<Output = C>,
+{
+    let n = network.graph.order();
+    let source = network.source.0;
+    let sink = network.sink.0;
+
+    if source == sink {
+        return C::zero();
+    }
+
+    let mut state = DinicState::new(n);
+    for edge_id in network.graph.edge_ids() {
+        let (src, dst) = network.graph.endpoints(edge_id);
+        state.add_arc(src.0, dst.0, network.capacity[edge_id.0]);
+    }
+
+    let mut max_flow = C::zero();
+    while state.build_levels(source, sink) {
+        state.next_arc.iter_mut().for_each(|i| *i = 0);
+        loop {
+            let pushed = state.push(source, sink, None);
+            if pushed <= C::zero() {
+                break;
+            }
+            max_flow = max_flow + pushed;
+        }
+    }
+
+    max_flow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdjacencyList, Simple, ford_fulkerson};
+
+    type Network = FlowNetwork<AdjacencyList<String, (), (), ()>, Simple, String, (), (), ()>;
+
+    #[test]
+    fn matches_ford_fulkerson() {
+        let networks = [
+            vec![
+                ("s", "v1", 0, 16),
+                ("s", "v2", 0, 13),
+                ("v1", "v3", 0, 12),
+                ("v2", "v1", 0, 4),
+                ("v2", "v4", 0, 14),
+                ("v3", "v2", 0, 9),
+                ("v3", "t", 0, 20),
+                ("v4", "v3", 0, 7),
+                ("v4", "t", 0, 4),
+            ],
+            vec![("s", "a", 0, 5), ("a", "s", 0, 3), ("a", "t", 0, 10)],
+            vec![("s", "a", 0, 5), ("s", "a", 0, 4), ("a", "t", 0, 10)],
+            // The initial flow does not change the maximum
+            vec![
+                ("s", "a", 2, 3),
+                ("a", "t", 2, 2),
+                ("s", "b", 0, 4),
+                ("b", "t", 0, 1),
+            ],
+        ];
+        for edges in networks {
+            let expected = ford_fulkerson(Network::from_edges(edges.clone(), "s", "t")).max_flow;
+            assert_eq!(dinic(Network::from_edges(edges, "s", "t")), expected);
+        }
+    }
+}
@@ -2,21 +2,23 @@ use std::{
     collections::{HashMap, VecDeque},
     fmt::{Debug, Display},
     hash::Hash,
-    ops::Add,
+    ops::{Add, Sub},
 };
 
 use crate::{
     DirectedGraph, EdgeId, GraphBase, GraphKindMarker, LatexDisplay, LatexVisualDisplay,
-    MutableStorage, NodeId, StorageRepresentation, VisualEdge, VisualGraphData,
+    MutableStorage, NodeId, StorageRepresentation, VisualEdge, VisualGraphData, Weight,
     generate_latex_graph,
 };
 
+/// Flow value on every ordered pair of nodes, in the capacity type `C`. Flows pushed back
+/// along an edge are recorded as negative values on the reverse pair.
 #[derive(Clone, Debug)]
-pub struct Flow {
-    map: HashMap<(NodeId, NodeId), i32>,
+pub struct Flow<C = i32> {
+    map: HashMap<(NodeId, NodeId), C>,
 }
 
-impl Flow {
+impl<C> Flow<C> {
     fn new() -> Self {
         Self {
             map: HashMap::new(),
@@ -24,33 +26,79 @@ impl Flow {
     }
 }
 
-impl Add<&Flow> for Flow {
-    type Output = Flow;
+impl<C: Weight> Add<&Flow<C>> for Flow<C> {
+    type Output = Flow<C>;
 
-    fn add(self, other: &Flow) -> Flow {
+    fn add(self, other: &Flow<C>) -> Flow<C> {
         let mut result = self.map.clone();
         for (&(src, dst), &f) in &other.map {
-            *result.entry((src, dst)).or_insert(0) += f;
+            let entry = result.entry((src, dst)).or_insert(C::zero());
+            *entry = *entry + f;
         }
         Flow { map: result }
     }
 }
 
+/// Inconsistency in the initial flow of a `FlowNetwork`, as found by `validate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FlowError<K, C = i32> {
+    /// The flow on the edge `from -> to` is negative or exceeds its capacity.
+    CapacityViolated {
+        from: K,
+        to: K,
+        flow: C,
+        capacity: C,
+    },
+    /// Flow entering `node` differs from the flow leaving it, at a node other than the
+    /// source and the sink.
+    NotConserved { node: K, inflow: C, outflow: C },
+}
+
+impl<K: Display, C: Display> Display for FlowError<K, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlowError::CapacityViolated {
+                from,
+                to,
+                flow,
+                capacity,
+            } => write!(
+                f,
+                "Flow {} on edge {} -> {} is outside of [0, {}]",
+                flow, from, to, capacity
+            ),
+            FlowError::NotConserved {
+                node,
+                inflow,
+                outflow,
+            } => write!(
+                f,
+                "Flow is not conserved at {}: {} in, {} out",
+                node, inflow, outflow
+            ),
+        }
+    }
+}
+
+/// Flow network over `graph`, with capacities and flows in `C` (integers by default).
 #[derive(Clone)]
-pub struct FlowNetwork<S, GK, K, D, E, W>
+pub struct FlowNetwork<S, GK, K, D, E, W, C = i32>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W> + Clone,
     GK: crate::traits::GraphKindMarker + Clone,
     K: Clone + Eq + std::hash::Hash,
 {
     pub graph: DirectedGraph<S, GK, K, D, E, W>,
-    pub capacity: Vec<u32>,
+    pub capacity: Vec<C>,
     pub source: NodeId,
     pub sink: NodeId,
-    pub flow: Flow,
+    pub flow: Flow<C>,
 }
 
-impl<S, GK, K, D, E, W> LatexVisualDisplay for FlowNetwork<S, GK, K, D, E, W>
+/// Flow network with integer capacities, as used before capacities became generic.
+pub type IntFlowNetwork<S, GK, K, D, E, W> = FlowNetwork<S, GK, K, D, E, W, i32>;
+
+impl<S, GK, K, D, E, W, C> LatexVisualDisplay for FlowNetwork<S, GK, K, D, E, W, C>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
         + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
@@ -60,21 +108,21 @@ where
     D: Debug + Clone,
     E: Debug + Clone + Default,
     W: Debug + Copy + PartialOrd,
+    C: Weight + Sub<Output = C> + Display,
 {
     fn to_latex_visual(&self) -> String {
         let mut network = self.clone();
         for ((from, to), value) in network.flow.map.iter() {
-            if *value > 0 && network.graph.edges_between(*from, *to).next().is_none() {
+            if *value > C::zero() && network.graph.edges_between(*from, *to).next().is_none() {
                 let c = network.capacity[network.graph.edges_between(*to, *from).next().unwrap().0]
-                    as i32
-                    + *network.flow.map.get(&(*to, *from)).unwrap_or(&0);
+                    + *network.flow.map.get(&(*to, *from)).unwrap_or(&C::zero());
 
-                if c > 0 {
+                if c > C::zero() {
                     network
                         .graph
                         .storage
                         .add_edge_by_id(*from, *to, E::default(), None);
-                    network.capacity.push(c as u32);
+                    network.capacity.push(c);
                 }
             }
         }
@@ -92,7 +140,7 @@ where
                 .map
                 .get(&(u, v))
                 .and_then(|f| {
-                    if *f > 0 {
+                    if *f > C::zero() {
                         Some(format!("{}/{}", f, network.capacity[eid.0]))
                     } else {
                         None
@@ -106,25 +154,22 @@ where
             });
         }
 
-        let data = VisualGraphData {
-            labels,
-            edges,
-            is_directed: true,
-        };
+        let data = VisualGraphData::new(labels, edges, true);
 
         generate_latex_graph(data)
     }
 }
 
-impl<S, GK, K, D, E, W> FlowNetwork<S, GK, K, D, E, W>
+impl<S, GK, K, D, E, W, C> FlowNetwork<S, GK, K, D, E, W, C>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W> + Clone,
     GK: crate::traits::GraphKindMarker + Clone,
     K: Clone + Eq + std::hash::Hash,
+    C: Weight + Sub<Output = C>,
 {
     pub fn new(
         graph: DirectedGraph<S, GK, K, D, E, W>,
-        capacity: Vec<u32>,
+        capacity: Vec<C>,
         source: NodeId,
         sink: NodeId,
     ) -> Self {
@@ -136,25 +181,78 @@ where
             flow: Flow::new(),
         }
     }
+
+    /// Checks that the flow on every edge lies between zero and its capacity, and that flow
+    /// is conserved at every node other than the source and the sink. Parallel edges share
+    /// their flow entry, so it is checked against their combined capacity.
+    pub fn validate(&self) -> Result<(), FlowError<K, C>>
+    where
+        K: Debug,
+        D: Debug + Clone,
+        E: Debug + Clone,
+        W: Debug + Copy + PartialOrd,
+    {
+        let mut capacities: HashMap<(NodeId, NodeId), C> = HashMap::new();
+        for edge_id in self.graph.edge_ids() {
+            let total = capacities
+                .entry(self.graph.endpoints(edge_id))
+                .or_insert(C::zero());
+            *total = *total + self.capacity[edge_id.0];
+        }
+
+        let mut inflow = vec![C::zero(); self.graph.order()];
+        let mut outflow = vec![C::zero(); self.graph.order()];
+        for edge_id in self.graph.edge_ids() {
+            let (src, dst) = self.graph.endpoints(edge_id);
+            let Some(capacity) = capacities.remove(&(src, dst)) else {
+                continue;
+            };
+            let flow = *self.flow.map.get(&(src, dst)).unwrap_or(&C::zero());
+            if flow < C::zero() || flow > capacity {
+                return Err(FlowError::CapacityViolated {
+                    from: self.graph.node_key(src).clone(),
+                    to: self.graph.node_key(dst).clone(),
+                    flow,
+                    capacity,
+                });
+            }
+            outflow[src.0] = outflow[src.0] + flow;
+            inflow[dst.0] = inflow[dst.0] + flow;
+        }
+
+        for node in self.graph.node_ids() {
+            if node != self.source && node != self.sink && inflow[node.0] != outflow[node.0] {
+                return Err(FlowError::NotConserved {
+                    node: self.graph.node_key(node).clone(),
+                    inflow: inflow[node.0],
+                    outflow: outflow[node.0],
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
-impl<S, GK, K> FlowNetwork<S, GK, K, (), (), ()>
+impl<S, GK, K, C> FlowNetwork<S, GK, K, (), (), (), C>
 where
     S: StorageRepresentation<Key = K, Data = (), EdgeMeta = (), Weight = ()>
         + MutableStorage<Key = K, Data = (), EdgeMeta = (), Weight = ()>
         + Clone,
     GK: crate::traits::GraphKindMarker + Clone,
     K: Clone + Eq + std::hash::Hash,
+    C: Weight + Sub<Output = C>,
 {
-    pub fn from_edges<UK>(edges: Vec<(UK, UK, i32, u32)>, source_key: UK, sink_key: UK) -> Self
+    /// Network from `(from, to, initial flow, capacity)` edges.
+    pub fn from_edges<UK>(edges: Vec<(UK, UK, C, C)>, source_key: UK, sink_key: UK) -> Self
     where
         UK: Into<K> + Clone,
     {
         let storage = S::with_node_capacity(edges.len() * 2);
         let mut graph = DirectedGraph::<S, GK, K, (), (), ()>::new(storage);
-        let mut capacity: Vec<u32> = Vec::new();
+        let mut capacity: Vec<C> = Vec::new();
 
-        let mut flow_map: HashMap<(NodeId, NodeId), i32> = HashMap::new();
+        let mut flow_map: HashMap<(NodeId, NodeId), C> = HashMap::new();
 
         for (from_key, to_key, flow, cap) in edges {
             let from_data = ();
@@ -170,8 +268,9 @@ where
             );
             capacity.push(cap);
 
-            flow_map.insert(
-                (
+            // Parallel edges share their flow entry, so their initial flows add up
+            let total = flow_map
+                .entry((
                     graph
                         .storage
                         .node_id(&from_key.clone().into())
@@ -180,9 +279,9 @@ where
                         .storage
                         .node_id(&to_key.clone().into())
                         .expect("To node key not found in graph"),
-                ),
-                flow,
-            );
+                ))
+                .or_insert(C::zero());
+            *total = *total + flow;
         }
 
         let source_id = graph
@@ -202,11 +301,83 @@ where
             flow: Flow { map: flow_map },
         }
     }
+
+    /// Like `from_edges`, but validates the initial flow and returns the first inconsistency.
+    pub fn from_edges_checked<UK>(
+        edges: Vec<(UK, UK, C, C)>,
+        source_key: UK,
+        sink_key: UK,
+    ) -> Result<Self, FlowError<K, C>>
+    where
+        UK: Into<K> + Clone,
+        K: Debug,
+    {
+        let network = Self::from_edges(edges, source_key, sink_key);
+        network.validate()?;
+        Ok(network)
+    }
+
+    /// Network with several sources and sinks, reduced to a single-source, single-sink one:
+    /// `super_source` gets an edge to every source and every sink an edge to `super_sink`.
+    /// These artificial edges never limit the flow, since their capacity is the total
+    /// capacity leaving their source (or entering their sink), and their initial flow keeps
+    /// the initial flow conserved. The flow value through the super nodes equals the flow
+    /// between the real sources and sinks.
+    pub fn from_edges_multi<UK>(
+        edges: Vec<(UK, UK, C, C)>,
+        sources: Vec<UK>,
+        sinks: Vec<UK>,
+        super_source: UK,
+        super_sink: UK,
+    ) -> Self
+    where
+        UK: Into<K> + Clone,
+    {
+        let mut edges: Vec<(K, K, C, C)> = edges
+            .into_iter()
+            .map(|(from, to, flow, cap)| (from.into(), to.into(), flow, cap))
+            .collect();
+
+        // Initial flow and total capacity of the edges leaving and entering every key.
+        let mut flow_out: HashMap<K, C> = HashMap::new();
+        let mut flow_in: HashMap<K, C> = HashMap::new();
+        let mut cap_out: HashMap<K, C> = HashMap::new();
+        let mut cap_in: HashMap<K, C> = HashMap::new();
+        let add = |totals: &mut HashMap<K, C>, key: &K, value: C| {
+            let total = totals.entry(key.clone()).or_insert(C::zero());
+            *total = *total + value;
+        };
+        for (from, to, flow, cap) in &edges {
+            add(&mut flow_out, from, *flow);
+            add(&mut flow_in, to, *flow);
+            add(&mut cap_out, from, *cap);
+            add(&mut cap_in, to, *cap);
+        }
+        let get = |totals: &HashMap<K, C>, key: &K| totals.get(key).copied().unwrap_or(C::zero());
+        let positive = |value: C| if value > C::zero() { value } else { C::zero() };
+
+        let super_source: K = super_source.into();
+        let super_sink: K = super_sink.into();
+        for source in sources {
+            let source: K = source.into();
+            let flow = positive(get(&flow_out, &source) - get(&flow_in, &source));
+            let cap = get(&cap_out, &source);
+            edges.push((super_source.clone(), source, flow, cap));
+        }
+        for sink in sinks {
+            let sink: K = sink.into();
+            let flow = positive(get(&flow_in, &sink) - get(&flow_out, &sink));
+            let cap = get(&cap_in, &sink);
+            edges.push((sink, super_sink.clone(), flow, cap));
+        }
+
+        Self::from_edges(edges, super_source, super_sink)
+    }
 }
 
-fn residual_network<S, GK, K, D, E, W>(
-    flow_network: &FlowNetwork<S, GK, K, D, E, W>,
-) -> FlowNetwork<S, GK, K, D, E, W>
+fn residual_network<S, GK, K, D, E, W, C>(
+    flow_network: &FlowNetwork<S, GK, K, D, E, W, C>,
+) -> FlowNetwork<S, GK, K, D, E, W, C>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
         + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
@@ -216,40 +387,51 @@ where
     D: Debug + Clone,
     E: Debug + Clone,
     W: Debug + Copy + PartialOrd,
+    C: Weight + Sub<Output = C>,
 {
     let mut residual_graph = flow_network.graph.clone();
     residual_graph.storage.clear_edges();
 
-    let mut residual_capacities: Vec<u32> = Vec::new();
+    let mut residual_capacities: Vec<C> = Vec::new();
 
+    // Parallel edges share their flow entry, so they are merged into one residual edge
+    // carrying their combined capacity, listed at the first of them.
+    let mut pairs: Vec<(NodeId, NodeId, EdgeId)> = Vec::new();
+    let mut capacities: HashMap<(NodeId, NodeId), C> = HashMap::new();
     for edge_id in flow_network.graph.edge_ids() {
         let (src, dst) = flow_network.graph.endpoints(edge_id);
-        let cap = flow_network.capacity[edge_id.0];
+        let total = capacities.entry((src, dst)).or_insert_with(|| {
+            pairs.push((src, dst, edge_id));
+            C::zero()
+        });
+        *total = *total + flow_network.capacity[edge_id.0];
+    }
 
-        let fwd_flow = *flow_network.flow.map.get(&(src, dst)).unwrap_or(&0);
+    for (src, dst, edge_id) in pairs {
+        let cap = capacities[&(src, dst)];
 
-        let new_capacity = cap as i32 - fwd_flow;
+        let fwd_flow = *flow_network.flow.map.get(&(src, dst)).unwrap_or(&C::zero());
 
-        if new_capacity > 0 {
+        let new_capacity = cap - fwd_flow;
+
+        if new_capacity > C::zero() {
             residual_graph.storage.add_edge_by_id(
                 src,
                 dst,
                 flow_network.graph.edge_meta(edge_id).clone(),
                 None,
             );
-            residual_capacities.push(new_capacity as u32);
+            residual_capacities.push(new_capacity);
         }
 
-        if flow_network.graph.edges_between(dst, src).next().is_none() {
-            if fwd_flow > 0 {
-                residual_graph.storage.add_edge_by_id(
-                    dst,
-                    src,
-                    flow_network.graph.edge_meta(edge_id).clone(),
-                    None,
-                );
-                residual_capacities.push(fwd_flow as u32);
-            }
+        if !capacities.contains_key(&(dst, src)) && fwd_flow > C::zero() {
+            residual_graph.storage.add_edge_by_id(
+                dst,
+                src,
+                flow_network.graph.edge_meta(edge_id).clone(),
+                None,
+            );
+            residual_capacities.push(fwd_flow);
         }
     }
 
@@ -262,7 +444,7 @@ where
     }
 }
 
-pub struct FordFulkersonResult<S, GK, K, D, E, W>
+pub struct FordFulkersonResult<S, GK, K, D, E, W, C = i32>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
         + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
@@ -273,18 +455,77 @@ where
     E: Debug + Clone,
     W: Debug + Copy + PartialOrd,
 {
-    pub max_flow: u32,
-    pub flow: Flow,
+    pub max_flow: C,
+    pub flow: Flow<C>,
+    /// The input network carrying the final flow (initial flow plus all augmentations).
+    pub network: FlowNetwork<S, GK, K, D, E, W, C>,
     pub steps: Vec<(
-        FlowNetwork<S, GK, K, D, E, W>,
-        Option<FlowNetwork<S, GK, K, D, E, W>>,
+        FlowNetwork<S, GK, K, D, E, W, C>,
+        Option<FlowNetwork<S, GK, K, D, E, W, C>>,
         Vec<K>,
-        u32,
+        C,
     )>,
     phantom: std::marker::PhantomData<K>,
 }
 
-impl<S, GK, K, D, E, W> LatexDisplay for FordFulkersonResult<S, GK, K, D, E, W>
+impl<S, GK, K, D, E, W, C> FordFulkersonResult<S, GK, K, D, E, W, C>
+where
+    S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + Clone,
+    GK: crate::traits::GraphKindMarker + Clone,
+    K: Debug + Clone + Eq + std::hash::Hash + Display,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+    C: Weight + Sub<Output = C>,
+{
+    /// Returns a minimum s-t cut as `(source side, sink side, cut edges)`.
+    ///
+    /// The source side is the set of nodes reachable from the source in the residual
+    /// network of the final step; the cut edges are the network edges leaving it.
+    /// The capacities of the cut edges sum up to `max_flow`.
+    pub fn min_cut(&self) -> (Vec<K>, Vec<K>, Vec<(K, K)>) {
+        let graph = &self.network.graph;
+        let mut reachable = vec![false; graph.order()];
+
+        if let Some((residual, _, _, _)) = self.steps.last() {
+            let mut queue = VecDeque::from([self.network.source]);
+            reachable[self.network.source.0] = true;
+
+            while let Some(current) = queue.pop_front() {
+                for neighbor in residual.graph.successors(current) {
+                    if !reachable[neighbor.0] {
+                        reachable[neighbor.0] = true;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut source_side = Vec::new();
+        let mut sink_side = Vec::new();
+        for node_id in graph.node_ids() {
+            let key = graph.node_key(node_id).clone();
+            if reachable[node_id.0] {
+                source_side.push(key);
+            } else {
+                sink_side.push(key);
+            }
+        }
+
+        let cut_edges = graph
+            .edge_ids()
+            .map(|edge_id| graph.endpoints(edge_id))
+            .filter(|(src, dst)| reachable[src.0] && !reachable[dst.0])
+            .map(|(src, dst)| (graph.node_key(src).clone(), graph.node_key(dst).clone()))
+            .collect();
+
+        (source_side, sink_side, cut_edges)
+    }
+}
+
+impl<S, GK, K, D, E, W, C> LatexDisplay for FordFulkersonResult<S, GK, K, D, E, W, C>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
         + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
@@ -294,7 +535,13 @@ where
     D: Debug + Clone,
     E: Debug + Clone + Default,
     W: Debug + Copy + PartialOrd,
+    C: Weight + Sub<Output = C> + Display,
 {
+    /// Every step draws the residual network.
+    fn required_packages(&self) -> &[&str] {
+        &["amsmath", "tikz"]
+    }
+
     fn to_latex(&self) -> String {
         let mut result = String::new();
         result.push_str(&format!(
@@ -303,7 +550,7 @@ where
         ));
         result.push_str("\\textbf{Flow Assignments:}\\\\\n");
         for (&(src, dst), &f) in &self.flow.map {
-            if f > 0 {
+            if f > C::zero() {
                 result.push_str(&format!("Flow from {} to {}: {}\\\\\n", src.0, dst.0, f));
             }
         }
@@ -329,9 +576,9 @@ where
     }
 }
 
-pub fn ford_fulkerson<S, GK, K, D, E, W>(
-    mut flow_network: FlowNetwork<S, GK, K, D, E, W>,
-) -> FordFulkersonResult<S, GK, K, D, E, W>
+pub fn ford_fulkerson<S, GK, K, D, E, W, C>(
+    mut flow_network: FlowNetwork<S, GK, K, D, E, W, C>,
+) -> FordFulkersonResult<S, GK, K, D, E, W, C>
 where
     S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>
         + MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
@@ -341,16 +588,17 @@ where
     D: Debug + Clone,
     E: Debug + Clone,
     W: Debug + Copy + PartialOrd,
+    C: Weight + Sub<Output = C>,
 {
     let mut networks = Vec::new();
 
     let original_flow = flow_network.flow.clone();
-    let mut flow: Flow = Flow::new();
+    let mut flow: Flow<C> = Flow::new();
 
     for edge_id in flow_network.graph.edge_ids() {
         let (src, dst) = flow_network.graph.endpoints(edge_id);
-        flow.map.insert((src, dst), 0);
-        flow.map.insert((dst, src), 0);
+        flow.map.insert((src, dst), C::zero());
+        flow.map.insert((dst, src), C::zero());
     }
 
     loop {
@@ -386,30 +634,35 @@ where
         }
 
         if !found_augmenting_path {
-            networks.push((residual_flow_network, None, Vec::new(), 0));
+            networks.push((residual_flow_network, None, Vec::new(), C::zero()));
 
             break;
         }
 
-        let mut path_capacity = u32::MAX;
+        let mut path_capacity: Option<C> = None;
         let mut v = sink_id;
         while let Some(u) = parent[&v] {
             let edge_ids: Vec<EdgeId> = residual_flow_network.graph.edges_between(u, v).collect();
             if let Some(edge_id) = edge_ids.first() {
                 let cap_index = edge_id.0;
                 let cap = residual_flow_network.capacity[cap_index];
-                path_capacity = path_capacity.min(cap);
+                if path_capacity.is_none_or(|current| cap < current) {
+                    path_capacity = Some(cap);
+                }
             }
             v = u;
         }
+        let path_capacity = path_capacity.expect("An augmenting path has at least one edge");
 
         let mut path_keys: Vec<K> = Vec::from_iter([flow_network.graph.node_key(sink_id).clone()]);
 
         v = sink_id;
         while let Some(u) = parent[&v] {
             path_keys.push(flow_network.graph.node_key(u).clone());
-            *flow.map.entry((u, v)).or_insert(0) += path_capacity as i32;
-            *flow.map.entry((v, u)).or_insert(0) -= path_capacity as i32;
+            let forward = flow.map.entry((u, v)).or_insert(C::zero());
+            *forward = *forward + path_capacity;
+            let backward = flow.map.entry((v, u)).or_insert(C::zero());
+            *backward = *backward - path_capacity;
             v = u;
         }
 
@@ -427,22 +680,120 @@ where
         ));
     }
 
-    let max_flow: u32 = flow
-        .map
-        .iter()
-        .filter_map(|(&(src, _), &f)| {
-            if src == flow_network.source {
-                Some(f)
-            } else {
-                None
-            }
-        })
-        .sum::<i32>() as u32;
+    // Net flow leaving the source, counted once per neighbour. The augmenting flow is
+    // skew-symmetric, so its entry for `(source, v)` already nets out antiparallel edges and
+    // is shared by parallel ones. The initial flow is stored per direction.
+    let mut neighbours: Vec<NodeId> = flow_network
+        .graph
+        .successors(flow_network.source)
+        .chain(flow_network.graph.predecessors(flow_network.source))
+        .filter(|&v| v != flow_network.source)
+        .collect();
+    neighbours.sort_unstable();
+    neighbours.dedup();
+
+    let value = |flow: &Flow<C>, from: NodeId, to: NodeId| {
+        flow.map.get(&(from, to)).copied().unwrap_or(C::zero())
+    };
+    let mut max_flow = C::zero();
+    for v in neighbours {
+        let s = flow_network.source;
+        max_flow = max_flow + value(&original_flow, s, v) - value(&original_flow, v, s)
+            + value(&flow, s, v);
+    }
 
     FordFulkersonResult {
         max_flow,
         flow,
+        network: flow_network,
         phantom: std::marker::PhantomData,
         steps: networks,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdjacencyList, Simple, dinic};
+
+    type Network<C = i32> =
+        FlowNetwork<AdjacencyList<String, (), (), ()>, Simple, String, (), (), (), C>;
+
+    fn max_flow(edges: Vec<(&str, &str, i32, i32)>) -> i32 {
+        ford_fulkerson(Network::from_edges(edges, "s", "t")).max_flow
+    }
+
+    #[test]
+    fn textbook_network() {
+        let edges = vec![
+            ("s", "v1", 0, 16),
+            ("s", "v2", 0, 13),
+            ("v1", "v3", 0, 12),
+            ("v2", "v1", 0, 4),
+            ("v2", "v4", 0, 14),
+            ("v3", "v2", 0, 9),
+            ("v3", "t", 0, 20),
+            ("v4", "v3", 0, 7),
+            ("v4", "t", 0, 4),
+        ];
+        assert_eq!(max_flow(edges), 23);
+    }
+
+    #[test]
+    fn antiparallel_edge_at_source() {
+        let edges = vec![("s", "a", 0, 5), ("a", "s", 0, 3), ("a", "t", 0, 10)];
+        assert_eq!(max_flow(edges), 5);
+    }
+
+    #[test]
+    fn parallel_edges_at_source() {
+        let edges = vec![("s", "a", 0, 5), ("s", "a", 0, 4), ("a", "t", 0, 10)];
+        assert_eq!(max_flow(edges), 9);
+    }
+
+    #[test]
+    fn initial_flow_on_parallel_edges_adds_up() {
+        let edges = vec![("s", "a", 2, 5), ("s", "a", 3, 4), ("a", "t", 5, 10)];
+        let network = Network::from_edges_checked(edges, "s", "t").unwrap();
+        let result = ford_fulkerson(network);
+        assert_eq!(result.max_flow, 9);
+        assert!(result.network.validate().is_ok());
+    }
+
+    /// Ford–Fulkerson and Dinic on the same edges, with the capacities converted by `cap`.
+    fn both<C>(edges: &[(&str, &str, i32)], cap: impl Fn(i32) -> C) -> (C, C)
+    where
+        C: Weight + Sub<Output = C>,
+    {
+        let edges: Vec<(&str, &str, C, C)> = edges
+            .iter()
+            .map(|&(from, to, c)| (from, to, C::zero(), cap(c)))
+            .collect();
+        (
+            ford_fulkerson(Network::<C>::from_edges(edges.clone(), "s", "t")).max_flow,
+            dinic(Network::<C>::from_edges(edges, "s", "t")),
+        )
+    }
+
+    #[test]
+    fn agrees_with_dinic_for_integer_and_float_capacities() {
+        let networks: [&[(&str, &str, i32)]; 3] = [
+            &[
+                ("s", "a", 10),
+                ("s", "b", 5),
+                ("a", "b", 15),
+                ("b", "a", 4),
+                ("a", "t", 6),
+                ("b", "t", 12),
+            ],
+            &[("s", "a", 5), ("a", "s", 3), ("a", "t", 10)],
+            &[("s", "a", 5), ("s", "a", 4), ("a", "b", 7), ("b", "t", 10)],
+        ];
+        for edges in networks {
+            let (ff, d) = both(edges, |c| c);
+            assert_eq!(ff, d);
+            let (ff, d) = both(edges, |c| c as f64 / 2.0);
+            assert!((ff - d).abs() < 1e-9);
+        }
+    }
+}
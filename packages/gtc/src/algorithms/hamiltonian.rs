@@ -0,0 +1,167 @@
+use crate::{Graph, NodeId};
+
+/// Distinct successors of every node, without self-loops.
+pub(crate) fn simple_successors<G>(graph: &G) -> Vec<Vec<usize>>
+where
+    G: Graph,
+{
+    graph
+        .node_ids()
+        .map(|u| {
+            let mut next: Vec<usize> = graph
+                .successors(u)
+                .map(|v| v.0)
+                .filter(|&v| v != u.0)
+                .collect();
+            next.sort_unstable();
+            next.dedup();
+            next
+        })
+        .collect()
+}
+
+/// Depth-first extension of `path` through unvisited nodes. When `close_to` is set the
+/// last node must also have an edge back to it.
+fn extend_path(
+    adj: &[Vec<usize>],
+    path: &mut Vec<usize>,
+    visited: &mut [bool],
+    close_to: Option<usize>,
+) -> bool {
+    let last = *path.last().expect("Path starts with one node");
+    if path.len() == adj.len() {
+        return close_to.is_none_or(|start| adj[last].contains(&start));
+    }
+
+    for &next in &adj[last] {
+        if visited[next] {
+            continue;
+        }
+        visited[next] = true;
+        path.push(next);
+        if extend_path(adj, path, visited, close_to) {
+            return true;
+        }
+        path.pop();
+        visited[next] = false;
+    }
+
+    false
+}
+
+fn search_from(adj: &[Vec<usize>], start: usize, close: bool) -> Option<Vec<usize>> {
+    let mut visited = vec![false; adj.len()];
+    visited[start] = true;
+    let mut path = vec![start];
+
+    extend_path(adj, &mut path, &mut visited, close.then_some(start)).then_some(path)
+}
+
+fn to_keys<G>(graph: &G, path: Vec<usize>) -> Vec<G::Key>
+where
+    G: Graph,
+{
+    path.into_iter()
+        .map(|i| graph.node_key(NodeId(i)).clone())
+        .collect()
+}
+
+/// Finds a cycle through every node exactly once by backtracking over successors, so edge
+/// directions are respected. The first node is repeated at the end of the returned sequence.
+/// Graphs with fewer than 3 nodes have no Hamiltonian cycle.
+///
+/// The search is exponential in the worst case and meant for small graphs. It gives up
+/// early when a node has fewer than two distinct neighbours, since it cannot be interior
+/// to a cycle.
+pub fn hamiltonian_cycle<G>(graph: &G) -> Option<Vec<G::Key>>
+where
+    G: Graph,
+{
+    let adj = simple_successors(graph);
+    if adj.len() < 3 || adj.iter().any(|next| next.is_empty()) {
+        return None;
+    }
+    if !graph.is_directed() && adj.iter().any(|next| next.len() < 2) {
+        return None;
+    }
+
+    let mut cycle = search_from(&adj, 0, true)?;
+    cycle.push(0);
+    Some(to_keys(graph, cycle))
+}
+
+/// Finds a path through every node exactly once by backtracking over successors, trying
+/// every start node. In undirected graphs a node of degree 1 can only be an endpoint, so
+/// if one exists the search starts from it alone.
+///
+/// The search is exponential in the worst case and meant for small graphs.
+pub fn hamiltonian_path<G>(graph: &G) -> Option<Vec<G::Key>>
+where
+    G: Graph,
+{
+    let adj = simple_successors(graph);
+    if adj.is_empty() {
+        return None;
+    }
+    if adj.len() == 1 {
+        return Some(to_keys(graph, vec![0]));
+    }
+
+    let mut starts: Vec<usize> = (0..adj.len()).collect();
+    if !graph.is_directed() {
+        let leaves: Vec<usize> = starts
+            .iter()
+            .copied()
+            .filter(|&v| adj[v].len() < 2)
+            .collect();
+        if leaves.len() > 2 || adj.iter().any(|next| next.is_empty()) {
+            return None;
+        }
+        if let Some(&leaf) = leaves.first() {
+            starts = vec![leaf];
+        }
+    }
+
+    starts
+        .into_iter()
+        .find_map(|start| search_from(&adj, start, false))
+        .map(|path| to_keys(graph, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphBase, GraphDefinition, Simple, UndirectedGraph};
+
+    type Undirected = UndirectedGraph<GraphDefinition<String>, Simple, String>;
+
+    fn follows_edges(graph: &Undirected, keys: &[String]) -> bool {
+        keys.windows(2).all(|pair| {
+            let from = graph.node_id(&pair[0]).unwrap();
+            let to = graph.node_id(&pair[1]).unwrap();
+            graph.edges_between(from, to).next().is_some()
+        })
+    }
+
+    #[test]
+    fn five_cycle() {
+        let c5 =
+            Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d"), ("d", "e"), ("e", "a")]);
+        let cycle = hamiltonian_cycle(&c5).expect("C5 is Hamiltonian");
+        assert_eq!(cycle.len(), 6);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(follows_edges(&c5, &cycle));
+        assert!(hamiltonian_path(&c5).is_some());
+    }
+
+    #[test]
+    fn four_path() {
+        let p4 = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d")]);
+        assert_eq!(hamiltonian_cycle(&p4), None);
+        let path = hamiltonian_path(&p4).expect("P4 is its own Hamiltonian path");
+        assert!(follows_edges(&p4, &path));
+        let mut ends = [&path[0], &path[3]];
+        ends.sort();
+        assert_eq!(ends, [&"a".to_string(), &"d".to_string()]);
+    }
+}
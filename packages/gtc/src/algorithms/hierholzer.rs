@@ -3,13 +3,31 @@ use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 use crate::{
-    EdgeId, GraphBase, GraphKindMarker, LatexDisplay, NodeId, StorageRepresentation,
+    EdgeId, Graph, GraphBase, GraphKindMarker, LatexDisplay, NodeId, StorageRepresentation,
     UndirectedGraph,
 };
 
 /// Result of Hierholzer's algorithm containing the Eulerian circuit path.
 pub struct HierholzerResult<K> {
     pub path: Vec<K>,
+    /// Edge taken at every step, so `edges[i]` joins `path[i]` and `path[i + 1]`.
+    /// Tells parallel edges apart where the node sequence cannot.
+    pub edges: Vec<EdgeId>,
+}
+
+impl<K: Display> HierholzerResult<K> {
+    /// Like `to_latex`, but labels every arrow with the id of the edge taken.
+    pub fn to_latex_with_edges(&self) -> String {
+        if self.path.is_empty() {
+            return "\\text{No Eulerian Circuit found}".to_string();
+        }
+
+        let mut result = self.path[0].to_string();
+        for (edge, key) in self.edges.iter().zip(&self.path[1..]) {
+            result.push_str(&format!(" \\xrightarrow{{e_{{{}}}}} {}", edge.0, key));
+        }
+        result
+    }
 }
 
 impl<K: Display> LatexDisplay for HierholzerResult<K> {
@@ -36,13 +54,20 @@ where
     E: Clone + Debug,
     W: Copy + PartialOrd + Debug,
 {
-    if graph.size() == 0 {
+    // Directed storages hold every edge as two opposite arcs; walk each edge once.
+    let edges = graph.logical_edge_ids();
+
+    if edges.is_empty() {
         if graph.order() > 0 {
             return Ok(HierholzerResult {
                 path: vec![graph.node_key(NodeId(0)).clone()],
+                edges: vec![],
             });
         }
-        return Ok(HierholzerResult { path: vec![] });
+        return Ok(HierholzerResult {
+            path: vec![],
+            edges: vec![],
+        });
     }
 
     let mut adjacency_list: HashMap<NodeId, Vec<EdgeId>> = HashMap::new();
@@ -53,7 +78,7 @@ where
         degree.insert(id, 0);
     }
 
-    for eid in graph.edge_ids() {
+    for &eid in &edges {
         let (u, v) = graph.endpoints(eid);
         adjacency_list.get_mut(&u).unwrap().push(eid);
         adjacency_list.get_mut(&v).unwrap().push(eid);
@@ -77,12 +102,20 @@ where
 
     let start_node = match start_node {
         Some(node) => node,
-        None => return Ok(HierholzerResult { path: vec![] }),
+        None => {
+            return Ok(HierholzerResult {
+                path: vec![],
+                edges: vec![],
+            });
+        }
     };
 
     let mut used_edges = HashSet::new();
     let mut circuit = Vec::new();
     let mut curr_path = vec![start_node];
+    // Edge used to reach each node of `curr_path` after the first, and likewise for `circuit`.
+    let mut circuit_edges = Vec::new();
+    let mut curr_edges = Vec::new();
 
     while let Some(&u) = curr_path.last() {
         let mut next_edge = None;
@@ -100,20 +133,107 @@ where
             let (v1, v2) = graph.endpoints(eid);
             let v = if v1 == u { v2 } else { v1 };
             curr_path.push(v);
+            curr_edges.push(eid);
         } else {
             circuit.push(curr_path.pop().unwrap());
+            if let Some(eid) = curr_edges.pop() {
+                circuit_edges.push(eid);
+            }
         }
     }
 
-    if circuit.len() != graph.size() + 1 {
+    if circuit.len() != edges.len() + 1 {
         return Err("Graph has disconnected components with edges.".to_string());
     }
 
     circuit.reverse();
+    circuit_edges.reverse();
     let path_keys = circuit
         .into_iter()
         .map(|id| graph.node_key(id).clone())
         .collect();
 
-    Ok(HierholzerResult { path: path_keys })
+    Ok(HierholzerResult {
+        path: path_keys,
+        edges: circuit_edges,
+    })
+}
+
+/// Which kind of Eulerian walk a graph admits, as decided by `eulerian_kind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EulerianKind {
+    /// A closed walk using every edge exactly once.
+    Circuit,
+    /// An open walk using every edge exactly once, but no closed one.
+    Path,
+    None,
+}
+
+/// Decides from degrees and connectivity whether the graph has an Eulerian circuit or path,
+/// without computing the walk. Undirected: every degree even gives a circuit, exactly two
+/// odd ones a path. Directed (`directed = true`): in-degree equal to out-degree everywhere
+/// gives a circuit, one node with one extra out-edge and one with one extra in-edge a path.
+/// Either way the edges must all lie in one component of the underlying graph.
+pub fn eulerian_kind<G>(graph: &G, directed: bool) -> EulerianKind
+where
+    G: Graph,
+{
+    let n = graph.order();
+    let mut out_degree = vec![0usize; n];
+    let mut in_degree = vec![0usize; n];
+    let mut adjacency: Vec<Vec<NodeId>> = vec![Vec::new(); n];
+
+    // Undirected graphs may store each edge as two arcs; count every edge once.
+    for eid in graph.logical_edge_ids() {
+        let (u, v) = graph.endpoints(eid);
+        out_degree[u.0] += 1;
+        in_degree[v.0] += 1;
+        adjacency[u.0].push(v);
+        adjacency[v.0].push(u);
+    }
+
+    // Every node with an edge must be reached from the first such node.
+    if let Some(start) = (0..n).find(|&v| !adjacency[v].is_empty()) {
+        let mut visited = vec![false; n];
+        visited[start] = true;
+        let mut stack = vec![start];
+        while let Some(u) = stack.pop() {
+            for &v in &adjacency[u] {
+                if !visited[v.0] {
+                    visited[v.0] = true;
+                    stack.push(v.0);
+                }
+            }
+        }
+        if (0..n).any(|v| !adjacency[v].is_empty() && !visited[v]) {
+            return EulerianKind::None;
+        }
+    }
+
+    if directed {
+        let mut extra_out = 0;
+        let mut extra_in = 0;
+        for v in 0..n {
+            match out_degree[v] as isize - in_degree[v] as isize {
+                0 => {}
+                1 => extra_out += 1,
+                -1 => extra_in += 1,
+                _ => return EulerianKind::None,
+            }
+        }
+        match (extra_out, extra_in) {
+            (0, 0) => EulerianKind::Circuit,
+            (1, 1) => EulerianKind::Path,
+            _ => EulerianKind::None,
+        }
+    } else {
+        let odd = (0..n)
+            .filter(|&v| (out_degree[v] + in_degree[v]) % 2 == 1)
+            .count();
+        match odd {
+            0 => EulerianKind::Circuit,
+            2 => EulerianKind::Path,
+            _ => EulerianKind::None,
+        }
+    }
 }
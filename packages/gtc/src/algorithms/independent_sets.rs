@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+use crate::{
+    Graph, NodeId,
+    colorings::WorkingGraph,
+    matching::{bipartite_partners, two_coloring},
+};
+
+/// Branch and bound over the remaining `candidates`. Nodes with at most one candidate
+/// neighbour are always safe to take; otherwise the search branches on the node of highest
+/// degree, first taking it and then discarding it.
+fn extend_independent_set(
+    wg: &WorkingGraph,
+    candidates: Vec<usize>,
+    current: &mut Vec<usize>,
+    best: &mut Vec<usize>,
+) {
+    if current.len() + candidates.len() <= best.len() {
+        return;
+    }
+    if candidates.is_empty() {
+        *best = current.clone();
+        return;
+    }
+
+    let degree = |v: usize| candidates.iter().filter(|&&u| wg.adj[v][u]).count();
+    let by_degree = |pick: fn(usize, usize) -> bool| {
+        let mut chosen = candidates[0];
+        for &v in &candidates[1..] {
+            if pick(degree(v), degree(chosen)) {
+                chosen = v;
+            }
+        }
+        chosen
+    };
+
+    let lowest = by_degree(|a, b| a < b);
+    let v = if degree(lowest) <= 1 {
+        lowest
+    } else {
+        by_degree(|a, b| a > b)
+    };
+
+    let without_neighborhood = candidates
+        .iter()
+        .copied()
+        .filter(|&u| u != v && !wg.adj[v][u])
+        .collect();
+    current.push(v);
+    extend_independent_set(wg, without_neighborhood, current, best);
+    current.pop();
+
+    if degree(v) > 1 {
+        let without_v = candidates.into_iter().filter(|&u| u != v).collect();
+        extend_independent_set(wg, without_v, current, best);
+    }
+}
+
+fn maximum_independent_set(wg: &WorkingGraph) -> Vec<usize> {
+    let mut best = Vec::new();
+    extend_independent_set(wg, (0..wg.n).collect(), &mut Vec::new(), &mut best);
+    best.sort_unstable();
+    best
+}
+
+/// König's theorem: with a maximum matching, the nodes reachable from unmatched `true`-side
+/// nodes by alternating paths are `Z`, and `(true side \ Z) ∪ (false side ∩ Z)` is a
+/// minimum vertex cover.
+fn konig_vertex_cover(wg: &WorkingGraph, side: &[bool]) -> Vec<usize> {
+    let partner = bipartite_partners(wg, side);
+
+    let mut reached = vec![false; wg.n];
+    let mut queue: VecDeque<usize> = (0..wg.n)
+        .filter(|&u| side[u] && partner[u].is_none())
+        .collect();
+    queue.iter().for_each(|&u| reached[u] = true);
+
+    while let Some(u) = queue.pop_front() {
+        for v in (0..wg.n).filter(|&v| wg.adj[u][v]) {
+            // Leave the left side along non-matching edges, return along matching ones.
+            if !reached[v] && side[u] != (partner[u] == Some(v)) {
+                reached[v] = true;
+                queue.push_back(v);
+            }
+        }
+    }
+
+    (0..wg.n).filter(|&v| side[v] != reached[v]).collect()
+}
+
+fn to_keys<G>(graph: &G, nodes: Vec<usize>) -> Vec<G::Key>
+where
+    G: Graph,
+{
+    nodes
+        .into_iter()
+        .map(|v| graph.node_key(NodeId(v)).clone())
+        .collect()
+}
+
+/// Returns a maximum independent set of the underlying simple undirected graph,
+/// found by branch and bound. The search is exponential and meant for small graphs.
+pub fn max_independent_set<G>(graph: &G) -> Vec<G::Key>
+where
+    G: Graph,
+{
+    to_keys(
+        graph,
+        maximum_independent_set(&WorkingGraph::from_graph(graph)),
+    )
+}
+
+/// Returns a minimum vertex cover of the underlying simple undirected graph.
+///
+/// Bipartite graphs are solved in polynomial time from a maximum matching by König's
+/// theorem. Other graphs fall back to the complement of `max_independent_set`, which is
+/// exponential in the worst case.
+pub fn min_vertex_cover<G>(graph: &G) -> Vec<G::Key>
+where
+    G: Graph,
+{
+    let wg = WorkingGraph::from_graph(graph);
+
+    let cover = match two_coloring(&wg) {
+        Some(side) => konig_vertex_cover(&wg, &side),
+        None => {
+            let independent = maximum_independent_set(&wg);
+            (0..wg.n)
+                .filter(|v| independent.binary_search(v).is_err())
+                .collect()
+        }
+    };
+
+    to_keys(graph, cover)
+}
@@ -0,0 +1,282 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt::Display;
+
+use crate::{
+    EdgeWeights, Graph, LatexDisplay, LatexMatrix, NodeId, WarshallLightestPathResult, Weight,
+    latex::MATRIX_PACKAGES,
+};
+
+/// Shortest-path distances between every ordered pair of nodes.
+/// `distances[i][j]` is `None` when node `j` is unreachable from node `i`.
+#[derive(Clone, Debug)]
+pub struct AllPairsDistances<K, W> {
+    pub nodes: Vec<K>,
+    pub distances: Vec<Vec<Option<W>>>,
+}
+
+impl<K, W> AllPairsDistances<K, W>
+where
+    K: PartialEq,
+    W: Copy,
+{
+    pub fn distance(&self, from: &K, to: &K) -> Option<W> {
+        let i = self.nodes.iter().position(|k| k == from)?;
+        let j = self.nodes.iter().position(|k| k == to)?;
+        self.distances[i][j]
+    }
+}
+
+impl<K, W> LatexDisplay for AllPairsDistances<K, W>
+where
+    K: Display,
+    W: Display,
+{
+    fn required_packages(&self) -> &[&str] {
+        MATRIX_PACKAGES
+    }
+
+    fn to_latex(&self) -> String {
+        let labels = self.nodes.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+
+        LatexMatrix {
+            data: &self.distances,
+            col_labels: labels.clone(),
+            row_labels: labels,
+            format_cell: &|cell| match cell {
+                Some(w) => w.to_string(),
+                None => "\\infty".to_string(),
+            },
+        }
+        .to_latex()
+    }
+}
+
+/// Floyd–Warshall distances from the final iteration of `warshall_lightest_path_matrix`.
+/// Every node is at distance zero from itself unless it lies on a negative cycle.
+impl<K, W> From<&WarshallLightestPathResult<K, W>> for AllPairsDistances<K, W>
+where
+    K: Clone,
+    W: Weight,
+{
+    fn from(result: &WarshallLightestPathResult<K, W>) -> Self {
+        let n = result.nodes.len();
+        let mut distances = vec![vec![None; n]; n];
+
+        if let Some(last) = result.matrices.last() {
+            for (i, row) in distances.iter_mut().enumerate() {
+                for (j, cell) in row.iter_mut().enumerate() {
+                    *cell = last.paths[i][j].as_ref().map(|(_, w)| *w);
+                }
+                if !row[i].is_some_and(|w| w < W::zero()) {
+                    row[i] = Some(W::zero());
+                }
+            }
+        }
+
+        AllPairsDistances {
+            nodes: result.nodes.clone(),
+            distances,
+        }
+    }
+}
+
+/// Hop-count distances between every ordered pair, found with a BFS over successors.
+pub fn hop_distances<G>(graph: &G) -> AllPairsDistances<G::Key, usize>
+where
+    G: Graph,
+{
+    let n = graph.order();
+    let nodes = graph
+        .node_ids()
+        .map(|id| graph.node_key(id).clone())
+        .collect();
+
+    let distances = (0..n)
+        .map(|source| {
+            let mut row = vec![None; n];
+            row[source] = Some(0);
+            let mut queue = VecDeque::from([NodeId(source)]);
+            while let Some(u) = queue.pop_front() {
+                let next = row[u.0].map(|d: usize| d + 1);
+                for v in graph.successors(u) {
+                    if row[v.0].is_none() {
+                        row[v.0] = next;
+                        queue.push_back(v);
+                    }
+                }
+            }
+            row
+        })
+        .collect();
+
+    AllPairsDistances { nodes, distances }
+}
+
+/// A cycle of negative total weight, which makes shortest paths undefined.
+/// The first node is repeated at the end.
+#[derive(Clone, Debug)]
+pub struct NegativeCycle<K> {
+    pub cycle: Vec<K>,
+}
+
+/// Bellman–Ford from a virtual source connected to every node with weight 0.
+/// Returns the potential `h(v)` of every node, or the node ids of a negative cycle.
+fn bellman_ford_potentials(
+    n: usize,
+    edges: &[(usize, usize, i64)],
+) -> Result<Vec<i64>, Vec<usize>> {
+    let mut potential = vec![0i64; n];
+    let mut predecessor: Vec<Option<usize>> = vec![None; n];
+
+    let mut last_relaxed = None;
+    for _ in 0..=n {
+        last_relaxed = None;
+        for &(u, v, w) in edges {
+            if potential[u] + w < potential[v] {
+                potential[v] = potential[u] + w;
+                predecessor[v] = Some(u);
+                last_relaxed = Some(v);
+            }
+        }
+        if last_relaxed.is_none() {
+            return Ok(potential);
+        }
+    }
+
+    // A relaxation in the final round means a negative cycle. Walking back n steps
+    // from the relaxed node is guaranteed to land on the cycle itself.
+    let mut v = last_relaxed.expect("A node was relaxed in the last round");
+    for _ in 0..n {
+        v = predecessor[v].expect("Relaxed nodes have a predecessor");
+    }
+
+    let mut cycle = vec![v];
+    let mut u = predecessor[v].expect("Cycle nodes have a predecessor");
+    while u != v {
+        cycle.push(u);
+        u = predecessor[u].expect("Cycle nodes have a predecessor");
+    }
+    cycle.push(v);
+    cycle.reverse();
+    Err(cycle)
+}
+
+/// Binary-heap Dijkstra over an adjacency list with non-negative weights.
+fn heap_dijkstra(adj: &[Vec<(usize, i64)>], source: usize) -> Vec<Option<i64>> {
+    let mut dist: Vec<Option<i64>> = vec![None; adj.len()];
+    let mut heap = BinaryHeap::new();
+
+    dist[source] = Some(0);
+    heap.push(Reverse((0i64, source)));
+
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if dist[u].is_some_and(|best| d > best) {
+            continue;
+        }
+        for &(v, w) in &adj[u] {
+            let alt = d + w;
+            if dist[v].is_none_or(|best| alt < best) {
+                dist[v] = Some(alt);
+                heap.push(Reverse((alt, v)));
+            }
+        }
+    }
+
+    dist
+}
+
+/// All-pairs shortest paths with Johnson's algorithm, suited to sparse graphs with
+/// negative edge weights: O(VE log V) instead of the O(V^3) of Floyd–Warshall.
+///
+/// Bellman–Ford from a virtual source yields potentials that reweight every edge to a
+/// non-negative weight, after which Dijkstra runs from each node. Edges without a weight
+/// are ignored. Fails with the offending cycle if the graph has a negative cycle
+/// (note that in undirected graphs any negative edge forms one).
+pub fn johnson<G>(graph: &G) -> Result<AllPairsDistances<G::Key, i32>, NegativeCycle<G::Key>>
+where
+    G: Graph + EdgeWeights<W = i32>,
+{
+    let n = graph.order();
+    let nodes: Vec<G::Key> = graph
+        .node_ids()
+        .map(|id| graph.node_key(id).clone())
+        .collect();
+
+    let edges: Vec<(usize, usize, i64)> = graph
+        .arcs()
+        .into_iter()
+        .filter_map(|(eid, u, v)| graph.weight_of(eid).map(|w| (u.0, v.0, w as i64)))
+        .collect();
+
+    let potential = bellman_ford_potentials(n, &edges).map_err(|cycle| NegativeCycle {
+        cycle: cycle
+            .into_iter()
+            .map(|i| graph.node_key(NodeId(i)).clone())
+            .collect(),
+    })?;
+
+    let mut adj: Vec<Vec<(usize, i64)>> = vec![Vec::new(); n];
+    for &(u, v, w) in &edges {
+        adj[u].push((v, w + potential[u] - potential[v]));
+    }
+
+    let distances = (0..n)
+        .map(|u| {
+            heap_dijkstra(&adj, u)
+                .into_iter()
+                .enumerate()
+                .map(|(v, d)| d.map(|d| (d - potential[u] + potential[v]) as i32))
+                .collect()
+        })
+        .collect();
+
+    Ok(AllPairsDistances { nodes, distances })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DirectedGraph, GraphDefinition, Simple, UndirectedAdjacencyList, UndirectedGraph,
+        warshall_lightest_path_matrix,
+    };
+
+    type Storage = GraphDefinition<String, (), (), i32>;
+
+    fn assert_matches_floyd_warshall<G>(graph: &G)
+    where
+        G: Graph + EdgeWeights<W = i32>,
+        G::Key: std::fmt::Debug,
+    {
+        let expected = AllPairsDistances::from(&warshall_lightest_path_matrix(graph));
+        let actual = johnson(graph).expect("No negative cycle");
+        assert_eq!(actual.nodes, expected.nodes);
+        assert_eq!(actual.distances, expected.distances);
+    }
+
+    #[test]
+    fn matches_floyd_warshall() {
+        let directed = DirectedGraph::<Storage, Simple, String, (), (), i32>::from_edges([
+            ("a", "b", 4),
+            ("a", "c", 2),
+            ("c", "b", -1),
+            ("b", "d", 3),
+            ("d", "a", 1),
+        ]);
+        assert_matches_floyd_warshall(&directed);
+
+        let edges = [("a", "b", 3), ("b", "c", 4), ("a", "c", 9), ("c", "d", 1)];
+        assert_matches_floyd_warshall(
+            &UndirectedGraph::<Storage, Simple, String, (), (), i32>::from_edges(edges),
+        );
+        assert_matches_floyd_warshall(&UndirectedGraph::<
+            UndirectedAdjacencyList<String, (), (), i32>,
+            Simple,
+            String,
+            (),
+            (),
+            i32,
+        >::from_edges(edges));
+    }
+}
@@ -4,8 +4,9 @@ use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 use crate::{
-    EdgeId, EdgeWeights, Graph, LatexDisplay, LatexVisualDisplay, VisualEdge, VisualGraphData,
-    generate_latex_graph,
+    EdgeId, EdgeWeights, Graph, LatexDisplay, LatexVisualDisplay, StorageRepresentation,
+    VisualEdge, VisualGraphData, WeightLabel, generate_latex_graph, latex::graph_visual_data,
+    util::UnionFind,
 };
 
 pub struct KruskalResult<K, W> {
@@ -33,6 +34,8 @@ where
     }
 }
 
+/// Draws only the tree edges and their endpoints, so isolated vertices are left out; use
+/// `to_latex_visual_on` to draw the whole source graph with the tree highlighted.
 impl<K, W> LatexVisualDisplay for KruskalResult<K, W>
 where
     K: Clone + Eq + Hash + Display,
@@ -66,59 +69,101 @@ where
             });
         }
 
-        let data = VisualGraphData {
-            labels,
-            edges: visual_edges,
-            is_directed: false,
-        };
+        let data = VisualGraphData::new(labels, visual_edges, false);
 
         generate_latex_graph(data)
     }
 }
 
-/// Helper Disjoint Set Union (DSU) / Union-Find data structure.
-struct UnionFind {
-    parent: Vec<usize>,
-    rank: Vec<usize>,
+impl<K, W> KruskalResult<K, W>
+where
+    K: Clone + Eq + Hash,
+{
+    /// Draws `graph` with the edges of this spanning tree highlighted. Every node of `graph`
+    /// is drawn, including the ones isolated in a spanning forest. `graph` must be the graph
+    /// Kruskal ran on.
+    pub fn to_latex_visual_on<G, S>(&self, graph: &G) -> String
+    where
+        G: Graph<Storage = S> + EdgeWeights,
+        S: StorageRepresentation<Key = K>,
+        K: Display,
+        G::W: WeightLabel,
+    {
+        let mut data = graph_visual_data(graph);
+        data.highlight_edges = self
+            .edges
+            .iter()
+            .map(|(u, v, _)| {
+                let u = graph.node_id(u).expect("Tree nodes belong to the graph");
+                let v = graph.node_id(v).expect("Tree nodes belong to the graph");
+                (u.0, v.0)
+            })
+            .collect();
+
+        generate_latex_graph(data)
+    }
 }
 
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            rank: vec![0; n],
+/// Every weighted edge with its weight. Undirected graphs may store each edge as two arcs;
+/// every edge is listed once.
+fn candidate_edges<G, W>(graph: &G) -> Vec<(EdgeId, W)>
+where
+    G: Graph + EdgeWeights<W = W>,
+{
+    graph
+        .logical_edge_ids()
+        .into_iter()
+        .filter_map(|eid| graph.weight_of(eid).map(|w| (eid, w)))
+        .collect()
+}
+
+/// Runs Kruskal over every weighted logical edge. Returns the chosen edges in the order
+/// they were picked, along with the union-find whose sets are the components.
+fn kruskal_edges<G, W>(graph: &G) -> (UnionFind, Vec<(EdgeId, W)>)
+where
+    G: Graph + EdgeWeights<W = W>,
+    G::Key: Eq + Hash,
+    W: Copy + PartialOrd,
+{
+    let mut edges = candidate_edges(graph);
+    edges.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+    let mut uf = UnionFind::new(graph.order());
+    let mut chosen = Vec::new();
+
+    for (eid, w) in edges {
+        let (u, v) = graph.endpoints(eid);
+        if uf.union(u.0, v.0) {
+            chosen.push((eid, w));
         }
     }
 
-    fn find(&mut self, i: usize) -> usize {
-        if self.parent[i] != i {
-            // Path compression: point directly to root
-            self.parent[i] = self.find(self.parent[i]);
-        }
-        self.parent[i]
+    (uf, chosen)
+}
+
+fn kruskal_result<G, W, I>(graph: &G, edges: I) -> KruskalResult<G::Key, W>
+where
+    G: Graph,
+    G::Key: Eq + Hash,
+    W: Copy + std::ops::Add<Output = W> + Default,
+    I: IntoIterator<Item = (EdgeId, W)>,
+{
+    let mut mst_edges = Vec::new();
+    let mut total_weight = W::default();
+    for (eid, w) in edges {
+        let (u, v) = graph.endpoints(eid);
+        mst_edges.push((graph.node_key(u).clone(), graph.node_key(v).clone(), w));
+        total_weight = total_weight + w;
     }
 
-    fn union(&mut self, i: usize, j: usize) -> bool {
-        let root_i = self.find(i);
-        let root_j = self.find(j);
-
-        if root_i != root_j {
-            // Union by rank: attach smaller tree to larger tree
-            match self.rank[root_i].cmp(&self.rank[root_j]) {
-                Ordering::Less => self.parent[root_i] = root_j,
-                Ordering::Greater => self.parent[root_j] = root_i,
-                Ordering::Equal => {
-                    self.parent[root_j] = root_i;
-                    self.rank[root_i] += 1;
-                }
-            }
-            true
-        } else {
-            false
-        }
+    KruskalResult {
+        edges: mst_edges,
+        total_weight,
     }
 }
 
+/// Minimum spanning tree, or a minimum spanning forest if the graph is disconnected; see
+/// `minimum_spanning_forest` to get one tree per component.
 pub fn kruskal_mst<G, W>(graph: &G) -> KruskalResult<G::Key, W>
 where
     G: Graph,
@@ -126,30 +171,135 @@ where
     G: EdgeWeights<W = W>,
     W: Copy + PartialOrd + std::ops::Add<Output = W> + Default + Debug,
 {
-    let mut edges: Vec<(EdgeId, W)> = Vec::new();
-    for eid in graph.edge_ids() {
-        if let Some(w) = graph.weight_of(eid) {
-            edges.push((eid, w));
-        }
+    let (_, edges) = kruskal_edges(graph);
+    kruskal_result(graph, edges)
+}
+
+/// One minimum spanning tree per connected component, ordered by the lowest node of each
+/// component. Isolated nodes give a tree without edges. Edges without a weight are ignored,
+/// so they do not join components.
+pub fn minimum_spanning_forest<G, W>(graph: &G) -> Vec<KruskalResult<G::Key, W>>
+where
+    G: Graph,
+    G::Key: Eq + Hash + Clone + Debug,
+    G: EdgeWeights<W = W>,
+    W: Copy + PartialOrd + std::ops::Add<Output = W> + Default + Debug,
+{
+    let (mut uf, edges) = kruskal_edges(graph);
+
+    let mut component_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut components: Vec<Vec<(EdgeId, W)>> = Vec::new();
+    for v in graph.node_ids() {
+        let root = uf.find(v.0);
+        component_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
     }
 
-    edges.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+    for (eid, w) in edges {
+        let (u, _) = graph.endpoints(eid);
+        components[component_of_root[&uf.find(u.0)]].push((eid, w));
+    }
+
+    components
+        .into_iter()
+        .map(|edges| kruskal_result(graph, edges))
+        .collect()
+}
+
+/// Minimum spanning tree (or forest) with Borůvka's algorithm: every round, each component
+/// picks its lightest outgoing edge and all picked edges are merged, until no component has
+/// one. Ties are broken by `EdgeId`, so the total weight matches `kruskal_mst`.
+pub fn boruvka_mst<G, W>(graph: &G) -> KruskalResult<G::Key, W>
+where
+    G: Graph,
+    G::Key: Eq + Hash + Clone + Debug,
+    G: EdgeWeights<W = W>,
+    W: Copy + PartialOrd + std::ops::Add<Output = W> + Default + Debug,
+{
+    let edges = candidate_edges(graph);
+
+    let lighter = |(a, wa): (EdgeId, W), (b, wb): (EdgeId, W)| match wa.partial_cmp(&wb) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Greater) => false,
+        _ => a < b,
+    };
 
     let mut uf = UnionFind::new(graph.order());
-    let mut mst_edges = Vec::new();
-    let mut total_weight = W::default();
+    let mut chosen = Vec::new();
 
-    for (eid, w) in edges {
-        let (u, v) = graph.endpoints(eid);
+    loop {
+        let mut cheapest: Vec<Option<(EdgeId, W)>> = vec![None; graph.order()];
+        for &(eid, w) in &edges {
+            let (u, v) = graph.endpoints(eid);
+            let (ru, rv) = (uf.find(u.0), uf.find(v.0));
+            if ru == rv {
+                continue;
+            }
+            for root in [ru, rv] {
+                if cheapest[root].is_none_or(|current| lighter((eid, w), current)) {
+                    cheapest[root] = Some((eid, w));
+                }
+            }
+        }
 
-        if uf.union(u.0, v.0) {
-            mst_edges.push((graph.node_key(u).clone(), graph.node_key(v).clone(), w));
-            total_weight = total_weight + w;
+        let mut merged = false;
+        for (eid, w) in cheapest.into_iter().flatten() {
+            let (u, v) = graph.endpoints(eid);
+            if uf.union(u.0, v.0) {
+                chosen.push((eid, w));
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
         }
     }
 
-    KruskalResult {
-        edges: mst_edges,
-        total_weight,
+    kruskal_result(graph, chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphBase, GraphDefinition, Simple, UndirectedGraph};
+
+    type Weighted =
+        UndirectedGraph<GraphDefinition<String, (), (), i32>, Simple, String, (), (), i32>;
+
+    #[test]
+    fn triangle_edges_are_considered_once() {
+        let triangle = Weighted::from_edges([("a", "b", 1), ("b", "c", 2), ("a", "c", 3)]);
+        assert_eq!(triangle.edge_ids().count(), 6);
+        assert_eq!(candidate_edges(&triangle).len(), 3);
+
+        let mst = kruskal_mst(&triangle);
+        assert_eq!(mst.edges.len(), 2);
+        assert_eq!(mst.total_weight, 3);
+    }
+
+    #[test]
+    fn boruvka_matches_kruskal() {
+        // K6 with many tied weights, and two components
+        let complete: Vec<(String, String, i32)> = (0..6)
+            .flat_map(|i| {
+                (i + 1..6).map(move |j| (i.to_string(), j.to_string(), (i * 7 + j * 3) % 5))
+            })
+            .collect();
+        let split = vec![
+            ("a".to_string(), "b".to_string(), 4),
+            ("b".to_string(), "c".to_string(), 1),
+            ("a".to_string(), "c".to_string(), 2),
+            ("d".to_string(), "e".to_string(), 7),
+        ];
+
+        for edges in [complete, split] {
+            let graph = Weighted::from_edges(edges);
+            let kruskal = kruskal_mst(&graph);
+            let boruvka = boruvka_mst(&graph);
+            assert_eq!(boruvka.total_weight, kruskal.total_weight);
+            assert_eq!(boruvka.edges.len(), kruskal.edges.len());
+        }
     }
 }
@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, NodeId, colorings::WorkingGraph};
+
+/// BFS 2-coloring of a simple undirected graph. `true` marks the side of the lowest node of
+/// every component. Returns `None` if an odd cycle makes the graph non-bipartite.
+pub(crate) fn two_coloring(wg: &WorkingGraph) -> Option<Vec<bool>> {
+    let mut side: Vec<Option<bool>> = vec![None; wg.n];
+
+    for start in 0..wg.n {
+        if side[start].is_some() {
+            continue;
+        }
+        side[start] = Some(true);
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(u) = queue.pop_front() {
+            let other = side[u].map(|s| !s);
+            for v in (0..wg.n).filter(|&v| wg.adj[u][v]) {
+                match side[v] {
+                    None => {
+                        side[v] = other;
+                        queue.push_back(v);
+                    }
+                    Some(s) if Some(s) != other => return None,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    Some(side.into_iter().map(|s| s.unwrap_or(true)).collect())
+}
+
+/// Tries to match `u` along an augmenting path (Kuhn's algorithm).
+fn augment(
+    wg: &WorkingGraph,
+    u: usize,
+    visited: &mut [bool],
+    partner: &mut [Option<usize>],
+) -> bool {
+    for v in 0..wg.n {
+        if !wg.adj[u][v] || visited[v] {
+            continue;
+        }
+        visited[v] = true;
+        if partner[v].is_none_or(|w| augment(wg, w, visited, partner)) {
+            partner[v] = Some(u);
+            partner[u] = Some(v);
+            return true;
+        }
+    }
+    false
+}
+
+/// Maximum matching between the `true` and `false` sides of a bipartite graph.
+/// `partner[v]` is the node matched with `v`, in both directions.
+pub(crate) fn bipartite_partners(wg: &WorkingGraph, side: &[bool]) -> Vec<Option<usize>> {
+    let mut partner = vec![None; wg.n];
+    for u in (0..wg.n).filter(|&u| side[u]) {
+        augment(wg, u, &mut vec![false; wg.n], &mut partner);
+    }
+    partner
+}
+
+/// Maximum matching of a bipartite graph with augmenting paths (Kuhn's algorithm), in
+/// O(V·E). Edge directions, self-loops and parallel edges are ignored. Each pair has its
+/// endpoint from the side of the lowest node of the component first. Returns `None` if
+/// the graph is not bipartite.
+pub fn bipartite_matching<G>(graph: &G) -> Option<Vec<(G::Key, G::Key)>>
+where
+    G: Graph,
+{
+    let wg = WorkingGraph::from_graph(graph);
+    let side = two_coloring(&wg)?;
+    let partner = bipartite_partners(&wg, &side);
+
+    Some(
+        (0..wg.n)
+            .filter(|&u| side[u])
+            .filter_map(|u| partner[u].map(|v| (u, v)))
+            .map(|(u, v)| {
+                (
+                    graph.node_key(NodeId(u)).clone(),
+                    graph.node_key(NodeId(v)).clone(),
+                )
+            })
+            .collect(),
+    )
+}
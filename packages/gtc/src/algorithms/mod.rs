@@ -1,15 +1,45 @@
+pub mod centrality;
+pub mod cliques;
 pub mod colorings;
+pub mod cores;
 pub mod dijkstra;
+pub mod dinic;
 pub mod ford_fulkerson;
+pub mod hamiltonian;
 pub mod hierholzer;
+pub mod independent_sets;
+pub mod johnson;
 pub mod kruskal;
+pub mod matching;
+pub mod paths;
+pub mod properties;
 pub mod prufer;
+pub mod spanning_trees;
+pub mod transformations;
+pub mod trees;
+pub mod tsp;
 pub mod warshall;
+pub mod yen;
 
+pub use centrality::*;
+pub use cliques::*;
 pub use colorings::*;
+pub use cores::*;
 pub use dijkstra::*;
+pub use dinic::*;
 pub use ford_fulkerson::*;
+pub use hamiltonian::*;
 pub use hierholzer::*;
+pub use independent_sets::*;
+pub use johnson::*;
 pub use kruskal::*;
+pub use matching::*;
+pub use paths::*;
+pub use properties::*;
 pub use prufer::*;
+pub use spanning_trees::*;
+pub use transformations::*;
+pub use trees::*;
+pub use tsp::*;
 pub use warshall::*;
+pub use yen::*;
@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, NodeId, hamiltonian::simple_successors};
+
+fn collect_paths(
+    adj: &[Vec<usize>],
+    dst: usize,
+    max_len: Option<usize>,
+    path: &mut Vec<usize>,
+    on_path: &mut [bool],
+    paths: &mut Vec<Vec<usize>>,
+) {
+    let last = *path.last().expect("Path starts with one node");
+    if last == dst {
+        paths.push(path.clone());
+        return;
+    }
+    // `path.len() - 1` edges so far
+    if max_len.is_some_and(|max| path.len() > max) {
+        return;
+    }
+
+    for &next in &adj[last] {
+        if on_path[next] {
+            continue;
+        }
+        on_path[next] = true;
+        path.push(next);
+        collect_paths(adj, dst, max_len, path, on_path, paths);
+        path.pop();
+        on_path[next] = false;
+    }
+}
+
+/// Every simple path from `src` to `dst`, following edge directions, as node sequences.
+/// Paths are listed in depth-first order with successors visited by node id, so the order
+/// is deterministic. Parallel edges yield the path once; `src == dst` gives the single
+/// path `[src]`.
+///
+/// The number of simple paths grows exponentially with the graph (a complete graph on `n`
+/// nodes has more than `(n - 2)!` between two nodes), so this is meant for small graphs.
+/// `max_len` caps the number of edges in a path, which also prunes the search.
+pub fn all_simple_paths<G>(
+    graph: &G,
+    src: G::Key,
+    dst: G::Key,
+    max_len: Option<usize>,
+) -> Vec<Vec<G::Key>>
+where
+    G: Graph,
+{
+    let src = graph.node_id(&src).expect("Source node not found in graph");
+    let dst = graph.node_id(&dst).expect("Target node not found in graph");
+
+    let adj = simple_successors(graph);
+    let mut on_path = vec![false; graph.order()];
+    on_path[src.0] = true;
+    let mut paths = Vec::new();
+    collect_paths(
+        &adj,
+        dst.0,
+        max_len,
+        &mut vec![src.0],
+        &mut on_path,
+        &mut paths,
+    );
+
+    paths
+        .into_iter()
+        .map(|path| {
+            path.into_iter()
+                .map(|i| graph.node_key(NodeId(i)).clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// A path from `src` to `dst` with the fewest edges, following edge directions, or `None`
+/// if `dst` is unreachable. A BFS over successors, so weights are ignored and none are
+/// needed.
+pub fn shortest_path_unweighted<G>(graph: &G, src: G::Key, dst: G::Key) -> Option<Vec<G::Key>>
+where
+    G: Graph,
+{
+    let src = graph.node_id(&src).expect("Source node not found in graph");
+    let dst = graph.node_id(&dst).expect("Target node not found in graph");
+
+    let mut predecessor: Vec<Option<NodeId>> = vec![None; graph.order()];
+    let mut visited = vec![false; graph.order()];
+    visited[src.0] = true;
+    let mut queue = VecDeque::from([src]);
+    while let Some(u) = queue.pop_front() {
+        if u == dst {
+            break;
+        }
+        for v in graph.successors(u) {
+            if !visited[v.0] {
+                visited[v.0] = true;
+                predecessor[v.0] = Some(u);
+                queue.push_back(v);
+            }
+        }
+    }
+    if !visited[dst.0] {
+        return None;
+    }
+
+    let mut path = vec![dst];
+    let mut current = dst;
+    while let Some(pred) = predecessor[current.0] {
+        path.push(pred);
+        current = pred;
+    }
+    path.reverse();
+
+    Some(
+        path.into_iter()
+            .map(|v| graph.node_key(v).clone())
+            .collect(),
+    )
+}
+
+/// Matrix product, or `None` as soon as an entry overflows.
+fn multiply(a: &[Vec<u64>], b: &[Vec<u64>]) -> Option<Vec<Vec<u64>>> {
+    let n = a.len();
+    let mut product = vec![vec![0u64; n]; n];
+    for i in 0..n {
+        for k in (0..n).filter(|&k| a[i][k] != 0) {
+            for j in 0..n {
+                product[i][j] = product[i][j].checked_add(a[i][k].checked_mul(b[k][j])?)?;
+            }
+        }
+    }
+    Some(product)
+}
+
+/// Number of walks with exactly `length` edges from node `i` to node `j`, at
+/// `[i][j]` by `NodeId`, following edge directions. Computed as the `length`-th power of
+/// the 0/1 adjacency matrix, so parallel edges count once and walks may repeat nodes and
+/// edges; these are not simple paths (see `all_simple_paths`). `length = 0` gives the
+/// identity matrix.
+///
+/// Walk counts grow exponentially with `length` (K_10 already overflows at 25), so this
+/// returns `None` if a count, or a power of the adjacency matrix computed on the way,
+/// does not fit in a `u64`.
+pub fn path_counts<G>(graph: &G, length: usize) -> Option<Vec<Vec<u64>>>
+where
+    G: Graph,
+{
+    let n = graph.order();
+    let mut adjacency = vec![vec![0; n]; n];
+    for u in graph.node_ids() {
+        for v in graph.successors(u) {
+            adjacency[u.0][v.0] = 1;
+        }
+    }
+
+    // Exponentiation by squaring
+    let mut counts: Vec<Vec<u64>> = (0..n)
+        .map(|i| (0..n).map(|j| u64::from(i == j)).collect())
+        .collect();
+    let mut power = adjacency;
+    let mut remaining = length;
+    while remaining > 0 {
+        if remaining % 2 == 1 {
+            counts = multiply(&counts, &power)?;
+        }
+        remaining /= 2;
+        if remaining > 0 {
+            power = multiply(&power, &power)?;
+        }
+    }
+
+    Some(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erdos_renyi;
+
+    #[test]
+    fn walks_in_complete_graph() {
+        // Closed walks of length 2 in K_n go out and back along any of the n - 1 edges
+        let k10 = erdos_renyi(10, 1.0, 0);
+        let counts = path_counts(&k10, 2).expect("Small counts fit");
+        assert_eq!((counts[0][0], counts[0][1]), (9, 8));
+        assert_eq!(path_counts(&k10, 0).unwrap()[3][3], 1);
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let k10 = erdos_renyi(10, 1.0, 0);
+        assert!(path_counts(&k10, 19).is_some());
+        assert_eq!(path_counts(&k10, 25), None);
+    }
+}
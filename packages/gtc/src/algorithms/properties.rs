@@ -0,0 +1,613 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    Graph, LatexDisplay, NodeId, colorings::WorkingGraph, matching::two_coloring, util::UnionFind,
+};
+
+/// Number of edges in the underlying graph. Degrees already account for
+/// undirected graphs storing every edge twice, so half their sum is the edge count.
+fn edge_count<G>(graph: &G) -> usize
+where
+    G: Graph,
+{
+    graph.node_ids().map(|v| graph.degree(v)).sum::<usize>() / 2
+}
+
+/// Number of connected components of the underlying undirected graph.
+fn component_count<G>(graph: &G) -> usize
+where
+    G: Graph,
+{
+    let mut visited = vec![false; graph.order()];
+    let mut components = 0;
+
+    for start in graph.node_ids() {
+        if visited[start.0] {
+            continue;
+        }
+        components += 1;
+        visited[start.0] = true;
+
+        let mut queue: VecDeque<NodeId> = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            for neighbor in graph.successors(current).chain(graph.predecessors(current)) {
+                if !visited[neighbor.0] {
+                    visited[neighbor.0] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// Whether every node can reach every other node when edge directions are ignored.
+/// The empty graph is considered connected.
+pub fn is_connected<G>(graph: &G) -> bool
+where
+    G: Graph,
+{
+    component_count(graph) <= 1
+}
+
+/// Whether the graph is acyclic when edge directions are ignored.
+/// Self-loops and parallel edges count as cycles.
+pub fn is_forest<G>(graph: &G) -> bool
+where
+    G: Graph,
+{
+    edge_count(graph) + component_count(graph) == graph.order()
+}
+
+/// Whether the graph is connected and has exactly `order() - 1` edges.
+/// The empty graph is not a tree.
+pub fn is_tree<G>(graph: &G) -> bool
+where
+    G: Graph,
+{
+    graph.order() > 0 && is_connected(graph) && edge_count(graph) == graph.order() - 1
+}
+
+/// Components of a graph, as computed by `weakly_connected_components`.
+#[derive(Clone, Debug)]
+pub struct ComponentResult<K> {
+    /// Component index of every node, by `NodeId`.
+    pub component_of: Vec<usize>,
+    /// The keys in each component, in node order. Components are ordered by their first node.
+    pub components: Vec<Vec<K>>,
+}
+
+impl<K> ComponentResult<K> {
+    pub fn count(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn same_component(&self, u: NodeId, v: NodeId) -> bool {
+        self.component_of[u.0] == self.component_of[v.0]
+    }
+}
+
+/// Components when edge directions are ignored, found by uniting the endpoints of every
+/// arc. For undirected graphs these are the connected components; for directed graphs they
+/// tell whether the graph is all one piece, unlike strongly connected components.
+pub fn weakly_connected_components<G>(graph: &G) -> ComponentResult<G::Key>
+where
+    G: Graph,
+{
+    let mut uf = UnionFind::new(graph.order());
+    for e in graph.edge_ids() {
+        let (from, to) = graph.endpoints(e);
+        uf.union(from.0, to.0);
+    }
+
+    let mut index_of_root = HashMap::new();
+    let mut component_of = Vec::with_capacity(graph.order());
+    let mut components: Vec<Vec<G::Key>> = Vec::new();
+    for v in graph.node_ids() {
+        let root = uf.find(v.0);
+        let index = *index_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        component_of.push(index);
+        components[index].push(graph.node_key(v).clone());
+    }
+
+    ComponentResult {
+        component_of,
+        components,
+    }
+}
+
+/// Connectivity of a graph whose edges arrive over time. Unlike `weakly_connected_components`,
+/// nothing is recomputed per query: each edge costs one union-find merge.
+pub struct ComponentTracker {
+    uf: UnionFind,
+}
+
+impl ComponentTracker {
+    /// `order` isolated nodes, with ids `NodeId(0)` to `NodeId(order - 1)`.
+    pub fn new(order: usize) -> Self {
+        Self {
+            uf: UnionFind::new(order),
+        }
+    }
+
+    /// Starts from the nodes and edges already in `graph`, ignoring edge directions.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: Graph,
+    {
+        let mut tracker = Self::new(graph.order());
+        for e in graph.edge_ids() {
+            let (from, to) = graph.endpoints(e);
+            tracker.add_edge(from, to);
+        }
+        tracker
+    }
+
+    /// Adds an isolated node, which gets the next id, `NodeId(order)`, like a node added to
+    /// the graph after the tracker was built.
+    pub fn add_node(&mut self) -> NodeId {
+        NodeId(self.uf.push())
+    }
+
+    /// Records an edge between `u` and `v`. Returns whether it joined two components.
+    pub fn add_edge(&mut self, u: NodeId, v: NodeId) -> bool {
+        self.uf.union(u.0, v.0)
+    }
+
+    pub fn connected(&mut self, u: NodeId, v: NodeId) -> bool {
+        self.uf.connected(u.0, v.0)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.uf.count()
+    }
+}
+
+/// Length of the shortest cycle, or `None` if there is none. A self-loop is a cycle of
+/// length 1. Directed graphs follow edge directions, so opposite arcs form a cycle of length
+/// 2. Undirected graphs count an edge once despite its two stored arcs, so only parallel
+/// edges give a cycle of length 2; forests have no girth.
+pub fn girth<G>(graph: &G) -> Option<usize>
+where
+    G: Graph,
+{
+    if graph.has_self_loop() {
+        return Some(1);
+    }
+    if graph.is_directed() {
+        return directed_girth(graph);
+    }
+    if graph.has_parallel_edges() {
+        return Some(2);
+    }
+
+    // BFS from every node: a non-tree edge u-v closes a cycle of length at most
+    // dist(u) + dist(v) + 1, and the minimum over all roots is exact.
+    let wg = WorkingGraph::from_graph(graph);
+    let mut shortest: Option<usize> = None;
+    for root in 0..wg.n {
+        let mut dist: Vec<Option<usize>> = vec![None; wg.n];
+        let mut parent = vec![usize::MAX; wg.n];
+        dist[root] = Some(0);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            let du = dist[u].expect("Queued nodes have a distance");
+            for v in (0..wg.n).filter(|&v| wg.adj[u][v]) {
+                match dist[v] {
+                    None => {
+                        dist[v] = Some(du + 1);
+                        parent[v] = u;
+                        queue.push_back(v);
+                    }
+                    Some(dv) if parent[u] != v => {
+                        let length = du + dv + 1;
+                        shortest = Some(shortest.map_or(length, |s| s.min(length)));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    shortest
+}
+
+/// Shortest directed cycle: for every root, the BFS distance to a predecessor of the root,
+/// plus the closing arc.
+fn directed_girth<G>(graph: &G) -> Option<usize>
+where
+    G: Graph,
+{
+    let mut shortest: Option<usize> = None;
+    for root in graph.node_ids() {
+        let mut dist: Vec<Option<usize>> = vec![None; graph.order()];
+        dist[root.0] = Some(0);
+        let mut queue = VecDeque::from([root]);
+        'bfs: while let Some(u) = queue.pop_front() {
+            let du = dist[u.0].expect("Queued nodes have a distance");
+            for v in graph.successors(u) {
+                if v == root {
+                    let length = du + 1;
+                    shortest = Some(shortest.map_or(length, |s| s.min(length)));
+                    break 'bfs;
+                }
+                if dist[v.0].is_none() {
+                    dist[v.0] = Some(du + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+
+    shortest
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// One directed cycle as the sequence of its nodes (the first node is not repeated at the
+/// end), or `None` if the graph is acyclic. A self-loop is a cycle of one node; undirected
+/// graphs store every edge as two arcs, so any edge is a cycle of two nodes there.
+pub fn find_cycle<G>(graph: &G) -> Option<Vec<G::Key>>
+where
+    G: Graph,
+{
+    let mut color = vec![Color::White; graph.order()];
+
+    for start in graph.node_ids() {
+        if color[start.0] != Color::White {
+            continue;
+        }
+        color[start.0] = Color::Gray;
+
+        // The gray nodes on the current DFS path, each with its successors not yet explored.
+        let mut stack: Vec<(NodeId, Vec<NodeId>)> =
+            vec![(start, graph.successors(start).collect())];
+        while let Some((node, pending)) = stack.last_mut() {
+            let node = *node;
+            let Some(next) = pending.pop() else {
+                color[node.0] = Color::Black;
+                stack.pop();
+                continue;
+            };
+
+            match color[next.0] {
+                Color::White => {
+                    color[next.0] = Color::Gray;
+                    stack.push((next, graph.successors(next).collect()));
+                }
+                Color::Gray => {
+                    let cycle_start = stack
+                        .iter()
+                        .position(|(v, _)| *v == next)
+                        .expect("Gray nodes are on the DFS path");
+                    return Some(
+                        stack[cycle_start..]
+                            .iter()
+                            .map(|(v, _)| graph.node_key(*v).clone())
+                            .collect(),
+                    );
+                }
+                Color::Black => {}
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of triangles through every node of the underlying simple undirected graph, by
+/// intersecting neighborhoods. Directions, self-loops and parallel edges are ignored.
+fn triangles_per_node(working: &WorkingGraph) -> Vec<u64> {
+    let neighbors: Vec<Vec<usize>> = working
+        .adj
+        .iter()
+        .map(|row| (0..working.n).filter(|&u| row[u]).collect())
+        .collect();
+
+    neighbors
+        .iter()
+        .map(|around| {
+            let mut count = 0;
+            for (i, &u) in around.iter().enumerate() {
+                for &w in &around[i + 1..] {
+                    if working.adj[u][w] {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        })
+        .collect()
+}
+
+/// Number of triangles in the graph, ignoring edge directions, self-loops and parallel edges.
+pub fn triangle_count<G>(graph: &G) -> u64
+where
+    G: Graph,
+{
+    // Every triangle is seen once from each of its corners
+    triangles_per_node(&WorkingGraph::from_graph(graph))
+        .iter()
+        .sum::<u64>()
+        / 3
+}
+
+/// Local clustering coefficient of every node, `2·t(v) / (d·(d - 1))` where `t(v)` counts the
+/// triangles through `v` and `d` is its number of distinct neighbors. Nodes with fewer than two
+/// neighbors get 0.
+pub fn clustering_coefficient<G>(graph: &G) -> Vec<(G::Key, f64)>
+where
+    G: Graph,
+{
+    let working = WorkingGraph::from_graph(graph);
+    let triangles = triangles_per_node(&working);
+
+    graph
+        .node_ids()
+        .map(|v| {
+            let d = working.adj[v.0].iter().filter(|&&a| a).count();
+            let coefficient = if d < 2 {
+                0.0
+            } else {
+                2.0 * triangles[v.0] as f64 / (d * (d - 1)) as f64
+            };
+            (graph.node_key(v).clone(), coefficient)
+        })
+        .collect()
+}
+
+/// Newman's degree assortativity: the Pearson correlation between the degrees at the two
+/// ends of every edge, from -1 (hubs join leaves) to 1 (hubs join hubs). Each edge is
+/// counted once in both orientations, so directed graphs are treated as undirected, with
+/// `degree` counting in- and out-edges. `None` if there are no edges or all edge ends have
+/// the same degree, where the correlation is undefined.
+pub fn degree_assortativity<G>(graph: &G) -> Option<f64>
+where
+    G: Graph,
+{
+    let edges = graph.logical_edge_ids();
+    if edges.is_empty() {
+        return None;
+    }
+
+    let (mut product, mut mean, mut square) = (0.0, 0.0, 0.0);
+    for &e in &edges {
+        let (u, v) = graph.endpoints(e);
+        let (j, k) = (graph.degree(u) as f64, graph.degree(v) as f64);
+        product += j * k;
+        mean += (j + k) / 2.0;
+        square += (j * j + k * k) / 2.0;
+    }
+    let m = edges.len() as f64;
+    let (product, mean, square) = (product / m, mean / m, square / m);
+
+    let variance = square - mean * mean;
+    if variance.abs() < 1e-12 {
+        return None;
+    }
+    Some((product - mean * mean) / variance)
+}
+
+/// Side 0 or 1 of every node in a 2-coloring, in node order, or `None` if the graph is not
+/// bipartite. Edge directions are ignored and a self-loop makes the graph non-bipartite.
+/// Each component is colored on its own, with its lowest node on side 0.
+pub fn bipartite_partition<G>(graph: &G) -> Option<Vec<(G::Key, u8)>>
+where
+    G: Graph,
+{
+    if graph.has_self_loop() {
+        return None;
+    }
+    let side = two_coloring(&WorkingGraph::from_graph(graph))?;
+
+    Some(
+        graph
+            .node_ids()
+            .map(|v| (graph.node_key(v).clone(), if side[v.0] { 0 } else { 1 }))
+            .collect(),
+    )
+}
+
+/// Overview of the basic metrics of a graph, as computed by `summarize`.
+#[derive(Clone, Debug)]
+pub struct GraphSummary {
+    pub order: usize,
+    /// Number of logical edges, counting every undirected edge once.
+    pub size: usize,
+    pub density: f64,
+    /// Degree extremes and mean; all zero for the empty graph.
+    pub min_degree: usize,
+    pub max_degree: usize,
+    pub average_degree: f64,
+    pub connected_components: usize,
+    pub is_tree: bool,
+    pub is_forest: bool,
+    pub is_bipartite: bool,
+    pub has_self_loops: bool,
+    pub has_parallel_edges: bool,
+}
+
+/// Computes every metric of `GraphSummary` in one call. Components, trees and
+/// bipartiteness ignore edge directions; a self-loop makes the graph non-bipartite.
+pub fn summarize<G>(graph: &G) -> GraphSummary
+where
+    G: Graph,
+{
+    let order = graph.order();
+    let edges = graph.logical_edge_ids();
+    let degrees: Vec<usize> = graph.node_ids().map(|v| graph.degree(v)).collect();
+
+    let has_self_loops = graph.has_self_loop();
+    let is_bipartite = bipartite_partition(graph).is_some();
+
+    GraphSummary {
+        order,
+        size: edges.len(),
+        density: graph.density(),
+        min_degree: degrees.iter().copied().min().unwrap_or(0),
+        max_degree: degrees.iter().copied().max().unwrap_or(0),
+        average_degree: if order == 0 {
+            0.0
+        } else {
+            degrees.iter().sum::<usize>() as f64 / order as f64
+        },
+        connected_components: component_count(graph),
+        is_tree: is_tree(graph),
+        is_forest: is_forest(graph),
+        is_bipartite,
+        has_self_loops,
+        has_parallel_edges: graph.has_parallel_edges(),
+    }
+}
+
+impl LatexDisplay for GraphSummary {
+    fn to_latex(&self) -> String {
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+        let rows = [
+            ("Order", self.order.to_string()),
+            ("Size", self.size.to_string()),
+            ("Density", format!("{:.3}", self.density)),
+            ("Minimum degree", self.min_degree.to_string()),
+            ("Maximum degree", self.max_degree.to_string()),
+            ("Average degree", format!("{:.3}", self.average_degree)),
+            (
+                "Connected components",
+                self.connected_components.to_string(),
+            ),
+            ("Tree", yes_no(self.is_tree).to_string()),
+            ("Forest", yes_no(self.is_forest).to_string()),
+            ("Bipartite", yes_no(self.is_bipartite).to_string()),
+            ("Self-loops", yes_no(self.has_self_loops).to_string()),
+            (
+                "Parallel edges",
+                yes_no(self.has_parallel_edges).to_string(),
+            ),
+        ];
+
+        let mut result = String::new();
+        result.push_str("\\begin{tabular}{|l|c|}\n\\hline\n");
+        result.push_str("Property & Value \\\\\n\\hline\n");
+        for (name, value) in rows {
+            result.push_str(&format!("{} & {} \\\\\n", name, value));
+        }
+        result.push_str("\\hline\n\\end{tabular}\n");
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DirectedGraph, GraphBase, GraphDefinition, Simple, UndirectedGraph};
+
+    type Undirected = UndirectedGraph<GraphDefinition<String>, Simple, String>;
+
+    #[test]
+    fn path_cycle_and_forest() {
+        let path = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d")]);
+        assert!(is_connected(&path) && is_tree(&path) && is_forest(&path));
+
+        let cycle = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]);
+        assert!(is_connected(&cycle));
+        assert!(!is_tree(&cycle) && !is_forest(&cycle));
+
+        let forest = Undirected::from_edges([("a", "b"), ("c", "d")]);
+        assert!(!is_connected(&forest) && !is_tree(&forest));
+        assert!(is_forest(&forest));
+    }
+
+    #[test]
+    fn directions_are_ignored() {
+        let in_tree = DirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges([
+            ("a", "b"),
+            ("c", "b"),
+        ]);
+        assert!(is_connected(&in_tree) && is_tree(&in_tree));
+    }
+
+    #[test]
+    fn triangles_and_clustering() {
+        let k4 = Undirected::from_edges([
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+        ]);
+        assert_eq!(triangle_count(&k4), 4);
+        assert!(clustering_coefficient(&k4).iter().all(|(_, c)| *c == 1.0));
+
+        // K_{2,3} is bipartite, so triangle-free
+        let bipartite = Undirected::from_edges([
+            ("a", "x"),
+            ("a", "y"),
+            ("a", "z"),
+            ("b", "x"),
+            ("b", "y"),
+            ("b", "z"),
+        ]);
+        assert_eq!(triangle_count(&bipartite), 0);
+        assert!(
+            clustering_coefficient(&bipartite)
+                .iter()
+                .all(|(_, c)| *c == 0.0)
+        );
+    }
+
+    #[test]
+    fn girth_of_cycle_complete_graph_and_tree() {
+        let c5 =
+            Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d"), ("d", "e"), ("e", "a")]);
+        assert_eq!(girth(&c5), Some(5));
+
+        let k4 = Undirected::from_edges([
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+        ]);
+        assert_eq!(girth(&k4), Some(3));
+
+        let tree = Undirected::from_edges([("a", "b"), ("a", "c"), ("c", "d")]);
+        assert_eq!(girth(&tree), None);
+
+        let directed = DirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+        ]);
+        assert_eq!(girth(&directed), Some(3));
+    }
+
+    #[test]
+    fn tracker_grows_with_the_graph() {
+        let mut graph = Undirected::from_edges([("a", "b")]);
+        let mut tracker = ComponentTracker::from_graph(&graph);
+        assert_eq!(tracker.component_count(), 1);
+
+        let (c, _) = graph
+            .add_edge_by_key_checked("c".into(), "a".into(), (), (), (), None)
+            .unwrap();
+        let c = graph.endpoints(c).0;
+        assert_eq!(tracker.add_node(), c);
+        assert_eq!(tracker.component_count(), 2);
+
+        let a = graph.node_id(&"a".to_string()).unwrap();
+        assert!(tracker.add_edge(c, a));
+        assert!(tracker.connected(c, NodeId(1)));
+        assert_eq!(tracker.component_count(), 1);
+    }
+}
@@ -3,12 +3,15 @@ use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 
-use crate::{Graph, GraphDefinition, NodeId};
+use crate::{Graph, GraphDefinition, NodeId, OrderedNodes};
 
+/// Computes the Prüfer sequence of a tree, removing the smallest leaf at every step.
+///
+/// Panics if the graph is not a tree; check with `is_tree` first when unsure.
 pub fn tree_to_prufer<G>(graph: &G) -> Vec<G::Key>
 where
     G: Graph,
-    G::Key: Clone + Eq + Hash + Ord + Debug,
+    G::Key: Clone + Eq + Hash + OrderedNodes + Debug,
 {
     let n = graph.order();
     if n < 2 {
@@ -68,51 +71,90 @@ where
     prufer_sequence
 }
 
-pub fn prufer_to_tree(sequence: &[usize]) -> GraphDefinition<usize, (), (), ()> {
+/// Reconstructs the labeled tree on `1..=sequence.len() + 2` encoded by a Prüfer sequence.
+///
+/// Returns an error instead of panicking when the sequence is malformed.
+pub fn prufer_to_tree(sequence: &[usize]) -> Result<GraphDefinition<usize, (), (), ()>, String> {
     let n = sequence.len() + 2;
-    let mut def = GraphDefinition::new();
-
-    for i in 1..=n {
-        def.add_node(i, ());
+    if let Some(&node) = sequence.iter().find(|&&node| node < 1 || node > n) {
+        return Err(format!(
+            "Invalid Prüfer sequence: node index {} out of bounds for range 1..={}",
+            node, n
+        ));
     }
 
-    if n == 2 {
-        def.add_edge_by_key(1, 2, (), (), (), None);
-        return def;
+    let vertices: Vec<usize> = (1..=n).collect();
+    prufer_to_tree_keyed(sequence, &vertices)
+}
+
+/// Reconstructs the tree on `vertices` encoded by a Prüfer sequence over arbitrary ordered keys.
+///
+/// This is the inverse of `tree_to_prufer`: leaves are selected by the keys' `Ord`, so
+/// decoding the sequence of a tree with its vertex set yields the same edge set.
+/// `vertices` must contain exactly `sequence.len() + 2` distinct keys, and every entry of
+/// the sequence must be one of them.
+pub fn prufer_to_tree_keyed<K>(
+    sequence: &[K],
+    vertices: &[K],
+) -> Result<GraphDefinition<K, (), (), ()>, String>
+where
+    K: Clone + Eq + Hash + OrderedNodes + Debug,
+{
+    let n = sequence.len() + 2;
+    if vertices.len() != n {
+        return Err(format!(
+            "Invalid Prüfer sequence: expected {} vertices for a sequence of length {}, got {}",
+            n,
+            sequence.len(),
+            vertices.len()
+        ));
     }
 
-    let mut degrees = vec![1; n + 1];
-    for &node in sequence {
-        if node < 1 || node > n {
-            panic!(
-                "Invalid Prüfer sequence: node index {} out of bounds for range 1..={}",
-                node, n
-            );
-        }
-        degrees[node] += 1;
+    let mut def = GraphDefinition::new();
+    for key in vertices {
+        def.add_node(key.clone(), ());
+    }
+    if def.order() != n {
+        return Err("Invalid Prüfer sequence: vertices must be distinct".to_string());
     }
 
-    let mut min_heap: BinaryHeap<Reverse<usize>> = BinaryHeap::new();
-    for i in 1..=n {
-        if degrees[i] == 1 {
-            min_heap.push(Reverse(i));
+    let mut degrees: HashMap<&K, usize> = vertices.iter().map(|k| (k, 1)).collect();
+    for key in sequence {
+        match degrees.get_mut(key) {
+            Some(d) => *d += 1,
+            None => {
+                return Err(format!(
+                    "Invalid Prüfer sequence: {:?} is not one of the vertices",
+                    key
+                ));
+            }
         }
     }
 
-    for &v in sequence {
-        let u = min_heap.pop().expect("Heap should not be empty").0;
+    let mut min_heap: BinaryHeap<Reverse<&K>> = vertices
+        .iter()
+        .filter(|k| degrees[k] == 1)
+        .map(Reverse)
+        .collect();
+
+    let empty_heap = || "Invalid Prüfer sequence: no leaf available to attach".to_string();
 
-        def.add_edge_by_key(u, v, (), (), (), None);
+    for v in sequence {
+        let u = min_heap.pop().ok_or_else(empty_heap)?.0;
 
-        degrees[v] -= 1;
-        if degrees[v] == 1 {
-            min_heap.push(Reverse(v));
+        def.add_edge_by_key(u.clone(), v.clone(), (), (), (), None);
+
+        if let Some(d) = degrees.get_mut(v) {
+            *d -= 1;
+            if *d == 1 {
+                min_heap.push(Reverse(v));
+            }
         }
     }
 
-    let u = min_heap.pop().unwrap().0;
-    let v = min_heap.pop().unwrap().0;
-    def.add_edge_by_key(u, v, (), (), (), None);
+    let u = min_heap.pop().ok_or_else(empty_heap)?.0;
+    let v = min_heap.pop().ok_or_else(empty_heap)?.0;
+    def.add_edge_by_key(u.clone(), v.clone(), (), (), (), None);
 
-    def
+    Ok(def)
 }
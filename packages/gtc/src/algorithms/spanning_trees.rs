@@ -0,0 +1,91 @@
+use crate::{Graph, colorings::WorkingGraph};
+
+/// Exact integer determinant using fraction-free Bareiss elimination.
+/// Every division is exact, so intermediate values stay integral.
+fn bareiss_determinant(mut m: Vec<Vec<i128>>) -> i128 {
+    let n = m.len();
+    if n == 0 {
+        return 1;
+    }
+
+    let mut sign = 1;
+    let mut prev_pivot = 1;
+
+    for k in 0..n - 1 {
+        if m[k][k] == 0 {
+            match (k + 1..n).find(|&i| m[i][k] != 0) {
+                Some(i) => {
+                    m.swap(k, i);
+                    sign = -sign;
+                }
+                None => return 0,
+            }
+        }
+
+        for i in k + 1..n {
+            for j in k + 1..n {
+                m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / prev_pivot;
+            }
+        }
+        prev_pivot = m[k][k];
+    }
+
+    sign * m[n - 1][n - 1]
+}
+
+/// Number of spanning trees of the underlying simple undirected graph, by Kirchhoff's
+/// Matrix-Tree theorem: the determinant of the Laplacian with row and column 0 removed.
+/// Self-loops and parallel edges are ignored. The empty graph has no spanning trees.
+pub fn spanning_tree_count<G>(graph: &G) -> u128
+where
+    G: Graph,
+{
+    let wg = WorkingGraph::from_graph(graph);
+    if wg.n == 0 {
+        return 0;
+    }
+
+    let minor: Vec<Vec<i128>> = (1..wg.n)
+        .map(|i| {
+            (1..wg.n)
+                .map(|j| {
+                    if i == j {
+                        wg.adj[i].iter().filter(|&&a| a).count() as i128
+                    } else if wg.adj[i][j] {
+                        -1
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    bareiss_determinant(minor) as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphDefinition, Simple, UndirectedGraph, erdos_renyi};
+
+    #[test]
+    fn cayley_formula() {
+        for n in 1..=8u32 {
+            let expected = if n == 1 { 1 } else { (n as u128).pow(n - 2) };
+            assert_eq!(
+                spanning_tree_count(&erdos_renyi(n as usize, 1.0, 0)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn cycle_and_disconnected_graph() {
+        type Undirected = UndirectedGraph<GraphDefinition<String>, Simple, String>;
+        let cycle = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]);
+        assert_eq!(spanning_tree_count(&cycle), 4);
+        let forest = Undirected::from_edges([("a", "b"), ("c", "d")]);
+        assert_eq!(spanning_tree_count(&forest), 0);
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::{
+    EdgeId, EdgeWeights, Graph, GraphBase, GraphDefinition, NodeId, Simple, StorageRepresentation,
+    UndirectedGraph, colorings::WorkingGraph,
+};
+
+/// Line graph L(G): every edge of the input becomes a node labeled by its `EdgeId`, and two
+/// nodes are adjacent iff the original edges share an endpoint. Undirected edges stored as
+/// two arcs yield a single node, labeled by the first of the pair.
+pub fn line_graph<G>(graph: &G) -> UndirectedGraph<GraphDefinition<usize>, Simple, usize>
+where
+    G: Graph,
+{
+    let edges = graph.logical_edge_ids();
+
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); graph.order()];
+    for (i, &e) in edges.iter().enumerate() {
+        let (u, v) = graph.endpoints(e);
+        incident[u.0].push(i);
+        if u != v {
+            incident[v.0].push(i);
+        }
+    }
+
+    let mut adjacent = BTreeSet::new();
+    for at_node in &incident {
+        for (k, &i) in at_node.iter().enumerate() {
+            for &j in &at_node[k + 1..] {
+                adjacent.insert((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    let mut storage: GraphDefinition<usize> = GraphDefinition::with_node_capacity(edges.len());
+    for e in &edges {
+        storage.add_node(e.0, ());
+    }
+
+    let mut line: UndirectedGraph<_, Simple, usize> = UndirectedGraph::new(storage);
+    for (i, j) in adjacent {
+        line.add_edge_checked(NodeId(i), NodeId(j), (), None)
+            .expect("Each pair of line graph nodes is added once");
+    }
+
+    line
+}
+
+/// Graph power G^k: the same nodes, adjacent iff their distance in G is between 1 and `k`.
+/// Distances are BFS hop counts with edge directions ignored, so `k = 1` gives the underlying
+/// simple graph and `k = 0` an edgeless one.
+pub fn graph_power<G>(
+    graph: &G,
+    k: usize,
+) -> UndirectedGraph<GraphDefinition<G::Key>, Simple, G::Key>
+where
+    G: Graph,
+    G::Key: Debug,
+{
+    let working = WorkingGraph::from_graph(graph);
+    let n = working.n;
+
+    let mut storage: GraphDefinition<G::Key> = GraphDefinition::with_node_capacity(n);
+    for v in graph.node_ids() {
+        storage.add_node(graph.node_key(v).clone(), ());
+    }
+
+    for source in 0..n {
+        let mut distance = vec![None; n];
+        distance[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            let d = distance[u].expect("Queued nodes have a distance");
+            if d == k {
+                continue;
+            }
+            for (v, &adjacent) in working.adj[u].iter().enumerate() {
+                if adjacent && distance[v].is_none() {
+                    distance[v] = Some(d + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        for (target, d) in distance.iter().enumerate().skip(source + 1) {
+            if d.is_some() {
+                storage.add_edge_by_id(NodeId(source), NodeId(target), (), None);
+                storage.add_edge_by_id(NodeId(target), NodeId(source), (), None);
+            }
+        }
+    }
+
+    UndirectedGraph::new(storage)
+}
+
+/// Simple undirected graph whose nodes are pairs of keys from the two factors.
+pub type ProductGraph<A, B> = UndirectedGraph<GraphDefinition<(A, B)>, Simple, (A, B)>;
+
+/// Shared construction for graph products. Factor nodes are ordered by key, so product nodes
+/// come out in lexicographic order. `adjacent(same_u, u_adj, same_v, v_adj)` decides whether
+/// `(u1, v1)` and `(u2, v2)` are joined, given how the components relate in their factors.
+fn graph_product<G, H>(
+    g: &G,
+    h: &H,
+    adjacent: impl Fn(bool, bool, bool, bool) -> bool,
+) -> ProductGraph<G::Key, H::Key>
+where
+    G: Graph,
+    H: Graph,
+    G::Key: Ord + Hash + Debug + Clone,
+    H::Key: Ord + Hash + Debug + Clone,
+{
+    let (g_adj, h_adj) = (
+        WorkingGraph::from_graph(g).adj,
+        WorkingGraph::from_graph(h).adj,
+    );
+
+    let mut g_nodes: Vec<usize> = (0..g.order()).collect();
+    let mut h_nodes: Vec<usize> = (0..h.order()).collect();
+    g_nodes.sort_by(|&a, &b| g.node_key(NodeId(a)).cmp(g.node_key(NodeId(b))));
+    h_nodes.sort_by(|&a, &b| h.node_key(NodeId(a)).cmp(h.node_key(NodeId(b))));
+
+    let pairs: Vec<(usize, usize)> = g_nodes
+        .iter()
+        .flat_map(|&u| h_nodes.iter().map(move |&v| (u, v)))
+        .collect();
+
+    let mut storage: GraphDefinition<(G::Key, H::Key)> =
+        GraphDefinition::with_node_capacity(pairs.len());
+    for &(u, v) in &pairs {
+        storage.add_node(
+            (g.node_key(NodeId(u)).clone(), h.node_key(NodeId(v)).clone()),
+            (),
+        );
+    }
+
+    for (i, &(u1, v1)) in pairs.iter().enumerate() {
+        for (j, &(u2, v2)) in pairs.iter().enumerate().skip(i + 1) {
+            if adjacent(u1 == u2, g_adj[u1][u2], v1 == v2, h_adj[v1][v2]) {
+                storage.add_edge_by_id(NodeId(i), NodeId(j), (), None);
+                storage.add_edge_by_id(NodeId(j), NodeId(i), (), None);
+            }
+        }
+    }
+
+    UndirectedGraph::new(storage)
+}
+
+/// Cartesian product G □ H: `(u1, v1)` and `(u2, v2)` are adjacent iff `u1 = u2` and
+/// `v1 ~ v2`, or `v1 = v2` and `u1 ~ u2`. Both factors are taken as simple undirected graphs,
+/// so e.g. a path times a path is a grid and repeated products of K2 give hypercubes.
+pub fn cartesian_product<G, H>(g: &G, h: &H) -> ProductGraph<G::Key, H::Key>
+where
+    G: Graph,
+    H: Graph,
+    G::Key: Ord + Hash + Debug + Clone,
+    H::Key: Ord + Hash + Debug + Clone,
+{
+    graph_product(g, h, |same_u, u_adj, same_v, v_adj| {
+        (same_u && v_adj) || (same_v && u_adj)
+    })
+}
+
+/// Tensor (categorical) product G × H: `(u1, v1)` and `(u2, v2)` are adjacent iff
+/// `u1 ~ u2` and `v1 ~ v2`. Both factors are taken as simple undirected graphs.
+pub fn tensor_product<G, H>(g: &G, h: &H) -> ProductGraph<G::Key, H::Key>
+where
+    G: Graph,
+    H: Graph,
+    G::Key: Ord + Hash + Debug + Clone,
+    H::Key: Ord + Hash + Debug + Clone,
+{
+    graph_product(g, h, |_, u_adj, _, v_adj| u_adj && v_adj)
+}
+
+/// Copy of the kept nodes, in node order, and of the kept edges between them.
+fn filtered<G>(
+    graph: &G,
+    keep_node: impl Fn(NodeId) -> bool,
+    keep_edge: impl Fn(EdgeId) -> bool,
+) -> GraphDefinition<G::Key, G::Data, G::EdgeMeta, G::Weight>
+where
+    G: Graph + EdgeWeights<W = <G as GraphBase>::Weight>,
+{
+    let mut storage = GraphDefinition::new();
+    let mut new_id: Vec<Option<NodeId>> = vec![None; graph.order()];
+    for v in graph.node_ids().filter(|&v| keep_node(v)) {
+        new_id[v.0] = Some(storage.add_node(graph.node_key(v).clone(), graph.node_data(v).clone()));
+    }
+
+    for e in graph.edge_ids().filter(|&e| keep_edge(e)) {
+        let (from, to) = graph.endpoints(e);
+        if let (Some(from), Some(to)) = (new_id[from.0], new_id[to.0]) {
+            storage.add_edge_by_id(from, to, graph.edge_meta(e).clone(), graph.weight_of(e));
+        }
+    }
+
+    storage
+}
+
+/// All nodes and the edges for which `pred` holds, e.g. `|e| g.weight_of(e) < Some(5)`.
+/// The predicate sees stored edges, so for undirected graphs over directed storages it
+/// should accept or reject both arcs of an edge alike.
+pub fn filter_edges<G, F>(
+    graph: &G,
+    pred: F,
+) -> GraphDefinition<G::Key, G::Data, G::EdgeMeta, G::Weight>
+where
+    G: Graph + EdgeWeights<W = <G as GraphBase>::Weight>,
+    F: Fn(EdgeId) -> bool,
+{
+    filtered(graph, |_| true, pred)
+}
+
+/// All nodes and the edges with weight at most `max_weight`, e.g. an epsilon-neighborhood
+/// graph to run components on. Edges without a weight are dropped.
+pub fn threshold_subgraph<G>(
+    graph: &G,
+    max_weight: G::Weight,
+) -> GraphDefinition<G::Key, G::Data, G::EdgeMeta, G::Weight>
+where
+    G: Graph + EdgeWeights<W = <G as GraphBase>::Weight>,
+{
+    filter_edges(graph, |e| {
+        graph.weight_of(e).is_some_and(|w| w <= max_weight)
+    })
+}
+
+/// The subgraph induced by the nodes for which `pred` holds: edges touching a removed node
+/// are dropped. Node ids are renumbered in node order.
+pub fn filter_nodes<G, F>(
+    graph: &G,
+    pred: F,
+) -> GraphDefinition<G::Key, G::Data, G::EdgeMeta, G::Weight>
+where
+    G: Graph + EdgeWeights<W = <G as GraphBase>::Weight>,
+    F: Fn(NodeId) -> bool,
+{
+    filtered(graph, pred, |_| true)
+}
+
+/// Copy of the graph with every key replaced by `f(key)`; node and edge ids are unchanged.
+/// Returns an error if two distinct keys map to the same new key.
+#[allow(clippy::type_complexity)]
+pub fn map_keys<G, K2, F>(
+    graph: &G,
+    f: F,
+) -> Result<GraphDefinition<K2, G::Data, G::EdgeMeta, G::Weight>, String>
+where
+    G: Graph + EdgeWeights<W = <G as GraphBase>::Weight>,
+    K2: Debug + Clone + Eq + Hash,
+    F: Fn(&G::Key) -> K2,
+{
+    let mut storage = GraphDefinition::new();
+    for v in graph.node_ids() {
+        let key = f(graph.node_key(v));
+        if storage.node_id(&key).is_some() {
+            return Err(format!(
+                "map_keys: {:?} is mapped to {:?}, which is already taken",
+                graph.node_key(v),
+                key
+            ));
+        }
+        storage.add_node(key, graph.node_data(v).clone());
+    }
+
+    for e in graph.edge_ids() {
+        let (from, to) = graph.endpoints(e);
+        storage.add_edge_by_id(from, to, graph.edge_meta(e).clone(), graph.weight_of(e));
+    }
+
+    Ok(storage)
+}
+
+/// Copy of the graph with every edge weight replaced by `f(weight)`, e.g. to scale weights
+/// or change their type. Edges without a weight keep none.
+pub fn map_weights<G, W2, F>(graph: &G, f: F) -> GraphDefinition<G::Key, G::Data, G::EdgeMeta, W2>
+where
+    G: Graph + EdgeWeights<W = <G as GraphBase>::Weight>,
+    W2: Debug + Copy + PartialOrd,
+    F: Fn(G::Weight) -> W2,
+{
+    let mut storage = GraphDefinition::new();
+    for v in graph.node_ids() {
+        storage.add_node(graph.node_key(v).clone(), graph.node_data(v).clone());
+    }
+
+    for e in graph.edge_ids() {
+        let (from, to) = graph.endpoints(e);
+        storage.add_edge_by_id(
+            from,
+            to,
+            graph.edge_meta(e).clone(),
+            graph.weight_of(e).map(&f),
+        );
+    }
+
+    storage
+}
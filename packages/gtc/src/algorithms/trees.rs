@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use crate::{
+    DirectedGraph, Graph, GraphDefinition, NodeId, Simple, StorageRepresentation,
+    colorings::WorkingGraph, is_tree,
+};
+
+/// The center of a tree: the one or two nodes left after repeatedly removing all leaves,
+/// i.e. the nodes of minimum eccentricity. Edge directions are ignored. Returns an empty
+/// list if the graph is not a tree, rather than panicking.
+pub fn tree_center<G>(graph: &G) -> Vec<G::Key>
+where
+    G: Graph,
+{
+    if !is_tree(graph) {
+        return Vec::new();
+    }
+
+    let working = WorkingGraph::from_graph(graph);
+    let mut degree: Vec<usize> = working
+        .adj
+        .iter()
+        .map(|row| row.iter().filter(|&&adjacent| adjacent).count())
+        .collect();
+    let mut layer: Vec<usize> = (0..working.n).filter(|&v| degree[v] <= 1).collect();
+    let mut remaining = working.n;
+
+    while remaining > 2 {
+        remaining -= layer.len();
+        let mut next = Vec::new();
+        for &leaf in &layer {
+            for v in (0..working.n).filter(|&v| working.adj[leaf][v]) {
+                degree[v] -= 1;
+                if degree[v] == 1 {
+                    next.push(v);
+                }
+            }
+        }
+        layer = next;
+    }
+
+    layer.sort_unstable();
+    layer
+        .into_iter()
+        .map(|v| graph.node_key(NodeId(v)).clone())
+        .collect()
+}
+
+/// The tree with every edge oriented away from `root`, keeping the node order of `graph`.
+/// Edge directions of `graph` are ignored.
+///
+/// Panics if the graph is not a tree; check with `is_tree` first when unsure.
+pub fn root_tree<G>(
+    graph: &G,
+    root: G::Key,
+) -> DirectedGraph<GraphDefinition<G::Key>, Simple, G::Key>
+where
+    G: Graph,
+    G::Key: Debug,
+{
+    assert!(is_tree(graph), "Graph is not a tree");
+    let root = graph.node_id(&root).expect("Root node not found in graph");
+
+    let working = WorkingGraph::from_graph(graph);
+    let mut storage: GraphDefinition<G::Key> = GraphDefinition::with_node_capacity(working.n);
+    for v in graph.node_ids() {
+        storage.add_node(graph.node_key(v).clone(), ());
+    }
+
+    let mut visited = vec![false; working.n];
+    visited[root.0] = true;
+    let mut queue = VecDeque::from([root.0]);
+    while let Some(u) = queue.pop_front() {
+        for v in (0..working.n).filter(|&v| working.adj[u][v]) {
+            if !visited[v] {
+                visited[v] = true;
+                storage.add_edge_by_id(NodeId(u), NodeId(v), (), None);
+                queue.push_back(v);
+            }
+        }
+    }
+
+    DirectedGraph::new(storage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphBase, UndirectedGraph};
+
+    type Undirected = UndirectedGraph<GraphDefinition<String>, Simple, String>;
+
+    #[test]
+    fn path_centers() {
+        let p4 = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d")]);
+        assert_eq!(tree_center(&p4), vec!["b", "c"]);
+
+        let p5 = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d"), ("d", "e")]);
+        assert_eq!(tree_center(&p5), vec!["c"]);
+
+        let cycle = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "a")]);
+        assert!(tree_center(&cycle).is_empty());
+    }
+
+    #[test]
+    fn rooting_orients_edges_away() {
+        let p4 = Undirected::from_edges([("a", "b"), ("b", "c"), ("c", "d")]);
+        let rooted = root_tree(&p4, "c".to_string());
+        let mut arcs: Vec<(&str, &str)> = rooted
+            .edge_ids()
+            .map(|e| {
+                let (from, to) = rooted.endpoints(e);
+                (rooted.node_key(from).as_str(), rooted.node_key(to).as_str())
+            })
+            .collect();
+        arcs.sort();
+        assert_eq!(arcs, vec![("b", "a"), ("c", "b"), ("c", "d")]);
+    }
+}
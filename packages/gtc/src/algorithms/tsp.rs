@@ -0,0 +1,167 @@
+use std::fmt::Display;
+
+use crate::{EdgeWeights, Graph, NodeId, Weight};
+
+/// Largest number of nodes accepted by `tsp_held_karp`; the DP table has `2^(n-1) * (n-1)` cells.
+pub const HELD_KARP_MAX_NODES: usize = 20;
+
+/// Input that `tsp_held_karp` refuses to solve.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TspError {
+    /// The graph has more than `HELD_KARP_MAX_NODES` nodes.
+    TooManyNodes { order: usize },
+}
+
+impl Display for TspError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TspError::TooManyNodes { order } => write!(
+                f,
+                "Held-Karp supports at most {} nodes, the graph has {}",
+                HELD_KARP_MAX_NODES, order
+            ),
+        }
+    }
+}
+
+/// Cost of a tour and the keys in visiting order, ending where it started.
+pub type Tour<W, K> = (W, Vec<K>);
+
+/// Keeps `candidate` in `slot` if it is lighter than what is already there.
+fn relax<W: Weight>(slot: &mut Option<(W, usize)>, candidate: W, from: usize) {
+    if slot.is_none_or(|(best, _)| candidate < best) {
+        *slot = Some((candidate, from));
+    }
+}
+
+/// Exact travelling salesman tour starting and ending at `start`, using the Held–Karp dynamic
+/// program over subsets in O(2^n · n^2) time. Edge directions are respected and the lightest
+/// of several parallel edges is used.
+///
+/// Returns the tour cost and the visiting order (with `start` repeated at the end), or `None`
+/// if `start` is not in the graph or no tour exists because some needed edge is missing.
+/// Graphs with more than `HELD_KARP_MAX_NODES` nodes give `TspError::TooManyNodes`.
+pub fn tsp_held_karp<G, W>(graph: &G, start: G::Key) -> Result<Option<Tour<W, G::Key>>, TspError>
+where
+    G: Graph + EdgeWeights<W = W>,
+    W: Weight,
+{
+    let n = graph.order();
+    if n > HELD_KARP_MAX_NODES {
+        return Err(TspError::TooManyNodes { order: n });
+    }
+
+    let Some(start) = graph.node_id(&start) else {
+        return Ok(None);
+    };
+    if n == 1 {
+        let key = graph.node_key(start).clone();
+        return Ok(Some((W::zero(), vec![key.clone(), key])));
+    }
+
+    // The other nodes are indexed 0..m for the bitmasks.
+    let others: Vec<NodeId> = graph.node_ids().filter(|&v| v != start).collect();
+    let m = others.len();
+    let lightest = |u: NodeId, v: NodeId| {
+        graph
+            .edges_between(u, v)
+            .filter_map(|e| graph.weight_of(e))
+            .fold(None, |best: Option<W>, w| match best {
+                Some(b) if b <= w => Some(b),
+                _ => Some(w),
+            })
+    };
+    let weights: Vec<Vec<Option<W>>> = (0..n)
+        .map(|u| (0..n).map(|v| lightest(NodeId(u), NodeId(v))).collect())
+        .collect();
+    let weight = |u: NodeId, v: NodeId| weights[u.0][v.0];
+
+    // best[mask][j]: lightest path from start through exactly `mask`, ending at others[j],
+    // together with the index of the node visited before j (m for the start itself).
+    let mut best: Vec<Vec<Option<(W, usize)>>> = vec![vec![None; m]; 1 << m];
+    for (j, &v) in others.iter().enumerate() {
+        if let Some(w) = weight(start, v) {
+            best[1 << j][j] = Some((w, m));
+        }
+    }
+
+    for mask in 1..(1usize << m) {
+        for j in 0..m {
+            let Some((cost, _)) = best[mask][j] else {
+                continue;
+            };
+            for k in 0..m {
+                if mask & (1 << k) != 0 {
+                    continue;
+                }
+                if let Some(w) = weight(others[j], others[k]) {
+                    relax(&mut best[mask | (1 << k)][k], cost + w, j);
+                }
+            }
+        }
+    }
+
+    let full = (1usize << m) - 1;
+    let mut closing: Option<(W, usize)> = None;
+    for (j, &v) in others.iter().enumerate() {
+        if let (Some((cost, _)), Some(w)) = (best[full][j], weight(v, start)) {
+            relax(&mut closing, cost + w, j);
+        }
+    }
+    let Some((total, mut last)) = closing else {
+        return Ok(None);
+    };
+
+    let mut order = Vec::with_capacity(n + 1);
+    let mut mask = full;
+    while last != m {
+        order.push(others[last]);
+        let (_, previous) = best[mask][last].expect("Tour nodes have a recorded path");
+        mask &= !(1 << last);
+        last = previous;
+    }
+    order.push(start);
+    order.reverse();
+    order.push(start);
+
+    Ok(Some((
+        total,
+        order
+            .into_iter()
+            .map(|id| graph.node_key(id).clone())
+            .collect(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphDefinition, Simple, UndirectedGraph};
+
+    type Weighted =
+        UndirectedGraph<GraphDefinition<usize, (), (), i32>, Simple, usize, (), (), i32>;
+
+    #[test]
+    fn square_tour_and_size_limit() {
+        // A 4-cycle of weight 4 whose diagonals cost 10
+        let square = Weighted::from_edges([
+            (0usize, 1usize, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 0, 1),
+            (0, 2, 10),
+            (1, 3, 10),
+        ]);
+        let (cost, tour) = tsp_held_karp(&square, 0).unwrap().unwrap();
+        assert_eq!(cost, 4);
+        assert!(tour == vec![0, 1, 2, 3, 0] || tour == vec![0, 3, 2, 1, 0]);
+        assert_eq!(tsp_held_karp(&square, 7), Ok(None));
+
+        let n = HELD_KARP_MAX_NODES + 1;
+        let path = Weighted::from_edges((1..n).map(|v| (v - 1, v, 1)));
+        assert_eq!(
+            tsp_held_karp(&path, 0),
+            Err(TspError::TooManyNodes { order: n })
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use std::hash::Hash;
 
-use crate::{Graph, LatexDisplay, LatexMatrix};
+use crate::{
+    AllPairsDistances, Graph, LatexDisplay, LatexMatrix, Weight, hop_distances,
+    latex::MATRIX_PACKAGES,
+};
 
 pub struct WarshallClosureResult<K> {
     pub nodes: Vec<K>,
@@ -8,6 +11,10 @@ pub struct WarshallClosureResult<K> {
 }
 
 impl<K: std::fmt::Display> LatexDisplay for WarshallClosureResult<K> {
+    fn required_packages(&self) -> &[&str] {
+        MATRIX_PACKAGES
+    }
+
     fn to_latex(&self) -> String {
         let labels = self.nodes.iter().map(|k| k.to_string()).collect::<Vec<_>>();
 
@@ -27,6 +34,34 @@ impl<K: std::fmt::Display> LatexDisplay for WarshallClosureResult<K> {
     }
 }
 
+impl<K: PartialEq + Clone> WarshallClosureResult<K> {
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.nodes.iter().position(|k| k == key)
+    }
+
+    /// Whether `to` is reachable from `from`; every node reaches itself. Unknown keys reach
+    /// nothing.
+    pub fn reaches(&self, from: &K, to: &K) -> bool {
+        match (self.index_of(from), self.index_of(to)) {
+            (Some(i), Some(j)) => self.closure[i][j],
+            _ => false,
+        }
+    }
+
+    /// Every node reachable from `from`, including itself, in node order.
+    pub fn reachable_from(&self, from: &K) -> Vec<K> {
+        let Some(i) = self.index_of(from) else {
+            return Vec::new();
+        };
+        self.closure[i]
+            .iter()
+            .zip(&self.nodes)
+            .filter(|(reachable, _)| **reachable)
+            .map(|(_, k)| k.clone())
+            .collect()
+    }
+}
+
 pub fn warshall_closure<G>(graph: &G) -> WarshallClosureResult<G::Key>
 where
     G: Graph,
@@ -39,8 +74,7 @@ where
         closure[i][i] = true;
     }
 
-    for edge_id in graph.edge_ids() {
-        let (src, dst) = graph.endpoints(edge_id);
+    for (_, src, dst) in graph.arcs() {
         closure[src.0][dst.0] = true;
     }
 
@@ -74,6 +108,10 @@ where
     K: std::fmt::Display,
     W: Copy + std::fmt::Display,
 {
+    fn required_packages(&self) -> &[&str] {
+        MATRIX_PACKAGES
+    }
+
     fn to_latex(&self) -> String {
         let labels = self.nodes.iter().map(|k| k.to_string()).collect::<Vec<_>>();
 
@@ -107,6 +145,10 @@ where
     K: std::fmt::Display,
     W: Copy + std::fmt::Display,
 {
+    fn required_packages(&self) -> &[&str] {
+        MATRIX_PACKAGES
+    }
+
     fn to_latex(&self) -> String {
         let mut result = String::new();
         for (i, matrix) in self.matrices.iter().enumerate() {
@@ -135,8 +177,7 @@ where
         paths,
     };
 
-    for edge_id in graph.edge_ids() {
-        let (src, dst) = graph.endpoints(edge_id);
+    for (edge_id, src, dst) in graph.arcs() {
         if let Some(weight) = graph.weight_of(edge_id) {
             warshall_path_matrix.paths[src.0][dst.0] = Some((vec![src.0, dst.0], weight));
         }
@@ -185,16 +226,20 @@ where
     }
 }
 
-pub struct GraphDistances<K> {
+pub struct GraphDistances<K, W = usize> {
     pub nodes: Vec<K>,
-    pub eccentricities: Vec<Option<usize>>,
-    pub radius: Option<usize>,
-    pub diameter: Option<usize>,
+    pub eccentricities: Vec<Option<W>>,
+    pub radius: Option<W>,
+    pub diameter: Option<W>,
 }
 
-impl<K> LatexDisplay for GraphDistances<K>
+/// Distances in the graph's own weight type, e.g. for the center of a weighted road network.
+pub type WeightedGraphDistances<K, W> = GraphDistances<K, W>;
+
+impl<K, W> LatexDisplay for GraphDistances<K, W>
 where
     K: std::fmt::Display,
+    W: Copy + PartialOrd + std::fmt::Display,
 {
     // radius, diameter, center nodes, periphery nodes, table of eccentricities
     fn to_latex(&self) -> String {
@@ -250,67 +295,185 @@ where
     }
 }
 
-impl<K> GraphDistances<K> {
+impl<K, W> GraphDistances<K, W>
+where
+    W: Copy + PartialOrd,
+{
     pub fn center_nodes(&self) -> Vec<usize> {
-        let mut centers = Vec::new();
-        if let Some(radius) = self.radius {
-            for (i, ecc) in self.eccentricities.iter().enumerate() {
-                if let Some(e) = ecc {
-                    if *e == radius {
-                        centers.push(i);
-                    }
-                }
-            }
+        match self.radius {
+            Some(radius) => self.nodes_with_eccentricity(radius),
+            None => Vec::new(),
         }
-        centers
     }
 
     pub fn periphery_nodes(&self) -> Vec<usize> {
-        let mut periphery = Vec::new();
-        if let Some(diameter) = self.diameter {
-            for (i, ecc) in self.eccentricities.iter().enumerate() {
-                if let Some(e) = ecc {
-                    if *e == diameter {
-                        periphery.push(i);
-                    }
-                }
-            }
+        match self.diameter {
+            Some(diameter) => self.nodes_with_eccentricity(diameter),
+            None => Vec::new(),
         }
-        periphery
+    }
+
+    fn nodes_with_eccentricity(&self, value: W) -> Vec<usize> {
+        self.eccentricities
+            .iter()
+            .enumerate()
+            .filter(|(_, ecc)| ecc.is_some_and(|e| e == value))
+            .map(|(i, _)| i)
+            .collect()
     }
 }
 
-pub fn compute_graph_distances<K>(matrix: &WarshallLightestPathResult<K, i32>) -> GraphDistances<K>
+/// Folds an iterator of weights into its minimum (or maximum) using the weight's own ordering.
+/// Incomparable values (e.g. NaN) never replace the current extremum.
+fn extremum<W, I>(values: I, keep: std::cmp::Ordering) -> Option<W>
 where
-    K: Clone,
+    W: Copy + PartialOrd,
+    I: IntoIterator<Item = W>,
 {
-    let n = matrix.matrices[0].nodes.len();
-    let mut eccentricities = vec![None; n];
+    values.into_iter().fold(None, |acc, w| match acc {
+        Some(current) if w.partial_cmp(&current) != Some(keep) => Some(current),
+        _ => Some(w),
+    })
+}
 
-    for i in 0..n {
-        let mut max_distance: Option<usize> = None;
-        for j in 0..n {
-            if i != j {
-                if let Some((_, weight)) = &matrix.matrices.last().unwrap().paths[i][j] {
-                    let dist = *weight as usize;
-                    max_distance = match max_distance {
-                        Some(current_max) => Some(current_max.max(dist)),
-                        None => Some(dist),
-                    };
-                }
-            }
+/// Eccentricities, radius and diameter from any all-pairs result, e.g. `johnson` or
+/// `hop_distances`. Unreachable nodes do not count towards a node's eccentricity.
+impl<K, W> From<&AllPairsDistances<K, W>> for GraphDistances<K, W>
+where
+    K: Clone,
+    W: Copy + PartialOrd,
+{
+    fn from(all_pairs: &AllPairsDistances<K, W>) -> Self {
+        let n = all_pairs.nodes.len();
+
+        let eccentricities: Vec<Option<W>> = (0..n)
+            .map(|i| {
+                let distances = (0..n)
+                    .filter(|&j| j != i)
+                    .filter_map(|j| all_pairs.distances[i][j]);
+                extremum(distances, std::cmp::Ordering::Greater)
+            })
+            .collect();
+
+        let radius = extremum(
+            eccentricities.iter().flatten().copied(),
+            std::cmp::Ordering::Less,
+        );
+        let diameter = extremum(
+            eccentricities.iter().flatten().copied(),
+            std::cmp::Ordering::Greater,
+        );
+
+        GraphDistances {
+            nodes: all_pairs.nodes.clone(),
+            eccentricities,
+            radius,
+            diameter,
         }
-        eccentricities[i] = max_distance;
     }
+}
+
+/// Computes eccentricities, radius and diameter from the final Warshall path matrix.
+/// Distances are kept in the weight type itself, so negative and fractional weights are supported.
+pub fn compute_graph_distances<K, W>(
+    matrix: &WarshallLightestPathResult<K, W>,
+) -> GraphDistances<K, W>
+where
+    K: Clone,
+    W: Weight,
+{
+    GraphDistances::from(&AllPairsDistances::from(matrix))
+}
+
+/// Edge weight types `graph_distances` can measure distances in.
+pub trait DistanceWeight: Sized {
+    type Distance;
+
+    fn graph_distances<G>(graph: &G) -> GraphDistances<G::Key, Self::Distance>
+    where
+        G: Graph + crate::EdgeWeights<W = Self>;
+}
+
+/// Unweighted graphs count hops, with a BFS from every node instead of Floyd–Warshall.
+impl DistanceWeight for () {
+    type Distance = usize;
+
+    fn graph_distances<G>(graph: &G) -> GraphDistances<G::Key>
+    where
+        G: Graph + crate::EdgeWeights<W = Self>,
+    {
+        GraphDistances::from(&hop_distances(graph))
+    }
+}
+
+/// Weighted graphs sum edge weights along lightest paths.
+impl<W> DistanceWeight for W
+where
+    W: Weight,
+{
+    type Distance = W;
+
+    fn graph_distances<G>(graph: &G) -> GraphDistances<G::Key, W>
+    where
+        G: Graph + crate::EdgeWeights<W = Self>,
+    {
+        compute_graph_distances(&warshall_lightest_path_matrix(graph))
+    }
+}
+
+/// Eccentricities, radius and diameter without the matrix plumbing: hop counts for unweighted
+/// graphs, and lightest path weights (via Floyd–Warshall) for weighted ones.
+pub fn graph_distances<G>(
+    graph: &G,
+) -> GraphDistances<G::Key, <<G as crate::EdgeWeights>::W as DistanceWeight>::Distance>
+where
+    G: Graph + crate::EdgeWeights,
+    <G as crate::EdgeWeights>::W: DistanceWeight,
+{
+    <G as crate::EdgeWeights>::W::graph_distances(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphDefinition, Simple, UndirectedAdjacencyList, UndirectedGraph};
 
-    let radius = eccentricities.iter().filter_map(|&e| e).min();
+    type Weighted<S> = UndirectedGraph<S, Simple, String, (), (), i32>;
 
-    let diameter = eccentricities.iter().filter_map(|&e| e).max();
+    const EDGES: [(&str, &str, i32); 2] = [("a", "b", 3), ("b", "c", 4)];
+
+    #[test]
+    fn symmetric_storage_matches_twin_arcs() {
+        let twins = Weighted::<GraphDefinition<String, (), (), i32>>::from_edges(EDGES);
+        let symmetric = Weighted::<UndirectedAdjacencyList<String, (), (), i32>>::from_edges(EDGES);
+
+        let expected = compute_graph_distances(&warshall_lightest_path_matrix(&twins));
+        let actual = compute_graph_distances(&warshall_lightest_path_matrix(&symmetric));
+        assert_eq!(expected.eccentricities, vec![Some(7), Some(4), Some(7)]);
+        assert_eq!(actual.eccentricities, expected.eccentricities);
+        assert_eq!((actual.radius, actual.diameter), (Some(4), Some(7)));
+
+        let c = "c".to_string();
+        assert_eq!(
+            warshall_closure(&symmetric).reachable_from(&c),
+            warshall_closure(&twins).reachable_from(&c)
+        );
+    }
 
-    GraphDistances {
-        nodes: matrix.nodes.clone(),
-        eccentricities,
-        radius,
-        diameter,
+    #[test]
+    fn graph_distances_use_weights_when_present() {
+        let weighted = Weighted::<GraphDefinition<String, (), (), i32>>::from_edges(EDGES);
+        assert_eq!(
+            graph_distances(&weighted).eccentricities,
+            vec![Some(7), Some(4), Some(7)]
+        );
+
+        let unweighted = UndirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges(
+            EDGES.map(|(a, b, _)| (a, b)),
+        );
+        assert_eq!(
+            graph_distances(&unweighted).eccentricities,
+            vec![Some(2), Some(1), Some(2)]
+        );
     }
 }
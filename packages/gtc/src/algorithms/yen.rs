@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::{DijkstraResult, EdgeWeights, Graph, NodeId, StorageRepresentation, dijkstra_with};
+
+/// Weight and nodes of the path to `target` recorded in a Dijkstra result.
+fn path_ids<K>(result: &DijkstraResult<K>, target: NodeId) -> Option<(i32, Vec<NodeId>)>
+where
+    K: Clone + Eq + Hash,
+{
+    let weight = result.tentative_weights[target.0]?;
+
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(pred) = result.predecessors[current.0] {
+        path.push(pred);
+        current = pred;
+    }
+    path.reverse();
+
+    Some((weight, path))
+}
+
+/// Total weight of a node path, taking the lightest edge between consecutive nodes.
+fn path_weight<G>(graph: &G, path: &[NodeId]) -> i32
+where
+    G: Graph + EdgeWeights<W = i32>,
+{
+    path.windows(2)
+        .map(|pair| {
+            graph
+                .edges_between(pair[0], pair[1])
+                .filter_map(|e| graph.weight_of(e))
+                .min()
+                .expect("Consecutive path nodes should be joined by a weighted edge")
+        })
+        .sum()
+}
+
+/// Up to `k` shortest loopless paths from `src` to `dst` (Yen's algorithm), sorted by total
+/// weight. Paths are node sequences; between parallel edges the lightest one is used. Paths
+/// of equal weight are returned in the order they were found.
+pub fn k_shortest_paths<G, S, K>(graph: &G, src: K, dst: K, k: usize) -> Vec<(i32, Vec<K>)>
+where
+    G: Graph<Storage = S> + EdgeWeights<W = i32>,
+    S: StorageRepresentation<Key = K, Weight = i32>,
+    K: Clone + Eq + Hash,
+{
+    let dst_id = graph.node_id(&dst).expect("Target node not found in graph");
+
+    let mut found: Vec<(i32, Vec<NodeId>)> = Vec::new();
+    let no_nodes = HashSet::new();
+    let no_edges = HashSet::new();
+    if k > 0 {
+        found.extend(path_ids(
+            &dijkstra_with(graph, src, &no_nodes, &no_edges),
+            dst_id,
+        ));
+    }
+
+    let mut candidates: Vec<(i32, Vec<NodeId>)> = Vec::new();
+    while !found.is_empty() && found.len() < k {
+        let previous = found.last().unwrap().1.clone();
+
+        for i in 0..previous.len() - 1 {
+            let spur = previous[i];
+            let root = &previous[..=i];
+
+            // Remove the next edge of every known path sharing this root, so the spur path
+            // has to deviate here
+            let excluded_edges: HashSet<_> = found
+                .iter()
+                .map(|(_, path)| path)
+                .filter(|path| path.len() > i + 1 && &path[..=i] == root)
+                .flat_map(|path| graph.edges_between(path[i], path[i + 1]))
+                .collect();
+            // Remove the root itself, so the result stays loopless
+            let excluded_nodes: HashSet<_> = root[..i].iter().copied().collect();
+
+            let result = dijkstra_with(
+                graph,
+                graph.node_key(spur).clone(),
+                &excluded_nodes,
+                &excluded_edges,
+            );
+            let Some((spur_weight, spur_path)) = path_ids(&result, dst_id) else {
+                continue;
+            };
+
+            let mut path = root[..i].to_vec();
+            path.extend(spur_path);
+            let weight = path_weight(graph, root) + spur_weight;
+
+            if !candidates.iter().any(|(_, p)| *p == path) && !found.iter().any(|(_, p)| *p == path)
+            {
+                candidates.push((weight, path));
+            }
+        }
+
+        let Some(best) = candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (weight, _))| *weight)
+            .map(|(index, _)| index)
+        else {
+            break;
+        };
+        found.push(candidates.remove(best));
+    }
+
+    found
+        .into_iter()
+        .map(|(weight, path)| {
+            (
+                weight,
+                path.into_iter()
+                    .map(|v| graph.node_key(v).clone())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphDefinition, Simple, UndirectedGraph};
+
+    type Weighted =
+        UndirectedGraph<GraphDefinition<String, (), (), i32>, Simple, String, (), (), i32>;
+
+    /// 3 x 3 lattice with unit weights and `"rc"` keys.
+    fn grid() -> Weighted {
+        let key = |r: usize, c: usize| format!("{r}{c}");
+        let mut edges = Vec::new();
+        for r in 0..3 {
+            for c in 0..3 {
+                if c + 1 < 3 {
+                    edges.push((key(r, c), key(r, c + 1), 1));
+                }
+                if r + 1 < 3 {
+                    edges.push((key(r, c), key(r + 1, c), 1));
+                }
+            }
+        }
+        Weighted::from_edges(edges)
+    }
+
+    #[test]
+    fn grid_corner_to_corner() {
+        let paths = k_shortest_paths(&grid(), "00".to_string(), "22".to_string(), 8);
+        assert_eq!(paths.len(), 8);
+
+        // The 6 monotone lattice paths come first, then detours of two extra steps
+        let weights: Vec<i32> = paths.iter().map(|(w, _)| *w).collect();
+        assert_eq!(weights, vec![4, 4, 4, 4, 4, 4, 6, 6]);
+
+        for (weight, path) in &paths {
+            assert_eq!(path.len() as i32, weight + 1);
+            assert_eq!(
+                (path[0].as_str(), path[path.len() - 1].as_str()),
+                ("00", "22")
+            );
+            let distinct: HashSet<&String> = path.iter().collect();
+            assert_eq!(distinct.len(), path.len(), "Paths are loopless");
+        }
+        let distinct: HashSet<&Vec<String>> = paths.iter().map(|(_, p)| p).collect();
+        assert_eq!(distinct.len(), paths.len());
+    }
+}
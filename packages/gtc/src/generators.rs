@@ -0,0 +1,178 @@
+//! Graph generators: structured families and seeded random graphs. The same seed always
+//! gives the same graph.
+
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{GraphDefinition, NodeId, Simple, UndirectedGraph, util::next_unit};
+
+type GeneratedGraph<K = usize> = UndirectedGraph<GraphDefinition<K>, Simple, K>;
+
+/// Isolated nodes with the given keys; the i-th key gets id `NodeId(i)`.
+fn isolated_nodes<K>(keys: impl IntoIterator<Item = K>) -> GeneratedGraph<K>
+where
+    K: Debug + Clone + Eq + Hash,
+{
+    let mut storage = GraphDefinition::new();
+    for key in keys {
+        storage.add_node(key, ());
+    }
+    UndirectedGraph::new(storage)
+}
+
+/// `rows` x `cols` lattice with `(row, col)` keys, where each node is joined to the nodes
+/// right of and below it. This is the Cartesian product of two paths.
+pub fn grid(rows: usize, cols: usize) -> GeneratedGraph<(usize, usize)> {
+    let mut graph = isolated_nodes((0..rows).flat_map(|r| (0..cols).map(move |c| (r, c))));
+    let id = |r: usize, c: usize| NodeId(r * cols + c);
+    for r in 0..rows {
+        for c in 0..cols {
+            if c + 1 < cols {
+                graph
+                    .add_edge_checked(id(r, c), id(r, c + 1), (), Some(()))
+                    .expect("Every lattice edge is added once");
+            }
+            if r + 1 < rows {
+                graph
+                    .add_edge_checked(id(r, c), id(r + 1, c), (), Some(()))
+                    .expect("Every lattice edge is added once");
+            }
+        }
+    }
+    graph
+}
+
+/// The `d`-dimensional hypercube Q_d: nodes are the integers `0..2^d`, joined when their
+/// binary representations differ in exactly one bit.
+pub fn hypercube(d: u32) -> GeneratedGraph {
+    let n = 1usize << d;
+    let mut graph = isolated_nodes(0..n);
+    for v in 0..n {
+        for bit in 0..d {
+            let u = v ^ (1 << bit);
+            if v < u {
+                graph
+                    .add_edge_checked(NodeId(v), NodeId(u), (), Some(()))
+                    .expect("Every pair is visited once");
+            }
+        }
+    }
+    graph
+}
+
+/// Erdős–Rényi graph G(n, p): every pair of the `n` nodes is joined independently with
+/// probability `p`, so `p = 1.0` gives the complete graph K_n.
+pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> GeneratedGraph {
+    let mut graph = isolated_nodes(0..n);
+    let mut state = seed;
+    for i in 0..n {
+        for j in i + 1..n {
+            if next_unit(&mut state) < p {
+                graph
+                    .add_edge_checked(NodeId(i), NodeId(j), (), Some(()))
+                    .expect("Every pair is visited once");
+            }
+        }
+    }
+    graph
+}
+
+/// Barabási–Albert scale-free graph: starts from a complete graph on `m + 1` nodes, then
+/// every new node joins `m` distinct existing nodes picked with probability proportional
+/// to their degree. With `n <= m + 1` this is just K_n.
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> GeneratedGraph {
+    assert!(m > 0, "Every new node needs at least one edge");
+
+    let mut graph = isolated_nodes(0..n);
+    let mut state = seed;
+    // Every edge puts both endpoints here, so a uniform pick is a degree-weighted one
+    let mut endpoints: Vec<usize> = Vec::new();
+
+    let seed_nodes = n.min(m + 1);
+    for i in 0..seed_nodes {
+        for j in i + 1..seed_nodes {
+            graph
+                .add_edge_checked(NodeId(i), NodeId(j), (), Some(()))
+                .expect("Every pair is visited once");
+            endpoints.extend([i, j]);
+        }
+    }
+
+    for v in seed_nodes..n {
+        let mut targets: Vec<usize> = Vec::with_capacity(m);
+        while targets.len() < m {
+            let pick = endpoints[(next_unit(&mut state) * endpoints.len() as f64) as usize];
+            if !targets.contains(&pick) {
+                targets.push(pick);
+            }
+        }
+        for t in targets {
+            graph
+                .add_edge_checked(NodeId(v), NodeId(t), (), Some(()))
+                .expect("Targets are distinct existing nodes");
+            endpoints.extend([v, t]);
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Graph, GraphBase};
+
+    fn edge_pairs<K>(graph: &GeneratedGraph<K>) -> Vec<(NodeId, NodeId)>
+    where
+        K: Debug + Clone + Eq + Hash,
+    {
+        graph
+            .logical_edge_ids()
+            .into_iter()
+            .map(|e| graph.endpoints(e))
+            .collect()
+    }
+
+    #[test]
+    fn erdos_renyi_with_certain_edges_is_complete() {
+        let k6 = erdos_renyi(6, 1.0, 42);
+        assert_eq!(k6.order(), 6);
+        assert_eq!(edge_pairs(&k6).len(), 15);
+        assert_eq!(k6.degree_sequence(), vec![5; 6]);
+        assert_eq!(edge_pairs(&erdos_renyi(6, 0.0, 42)), vec![]);
+    }
+
+    #[test]
+    fn same_seed_same_graph() {
+        assert_eq!(
+            edge_pairs(&erdos_renyi(30, 0.2, 7)),
+            edge_pairs(&erdos_renyi(30, 0.2, 7))
+        );
+        assert_ne!(
+            edge_pairs(&erdos_renyi(30, 0.2, 7)),
+            edge_pairs(&erdos_renyi(30, 0.2, 8))
+        );
+        assert_eq!(
+            edge_pairs(&barabasi_albert(30, 2, 7)),
+            edge_pairs(&barabasi_albert(30, 2, 7))
+        );
+    }
+
+    #[test]
+    fn grid_and_hypercube_shapes() {
+        let square = grid(2, 2);
+        assert_eq!(square.order(), 4);
+        assert_eq!(
+            edge_pairs(&square),
+            [(0, 1), (0, 2), (1, 3), (2, 3)].map(|(a, b)| (NodeId(a), NodeId(b)))
+        );
+        assert_eq!(square.node_key(NodeId(3)), &(1, 1));
+
+        let q3 = hypercube(3);
+        assert_eq!(q3.order(), 8);
+        assert_eq!(edge_pairs(&q3).len(), 12);
+        assert_eq!(q3.degree_sequence(), vec![3; 8]);
+        for (a, b) in edge_pairs(&q3) {
+            assert_eq!((a.0 ^ b.0).count_ones(), 1);
+        }
+    }
+}
@@ -49,6 +49,15 @@ where
         id
     }
 
+    /// Intern key + data. If key already exists, its data is replaced and the existing NodeId returned.
+    pub fn intern_or_update(&mut self, key: K, data: D) -> NodeId {
+        if let Some(&id) = self.index.get(&key) {
+            self.records[id.0].data = data;
+            return id;
+        }
+        self.intern(key, data)
+    }
+
     pub fn len(&self) -> usize {
         self.records.len()
     }
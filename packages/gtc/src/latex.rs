@@ -1,21 +1,106 @@
-use core::panic;
 use std::{
     fmt::{Debug, Display},
     hash::Hash,
 };
 
 use crate::{
-    AdjacencyMatrix, DirectedGraph, EdgeWeights, GraphBase, GraphDefinition, GraphKindMarker,
-    NodeId, StorageRepresentation, UndirectedGraph, VisualEdge, VisualGraphData,
-    generate_latex_graph,
+    AdjacencyMatrix, DirectedGraph, EdgeId, EdgeWeights, Graph, GraphBase, GraphDefinition,
+    GraphKindMarker, NodeId, StorageRepresentation, UndirectedGraph, VisualEdge, VisualGraphData,
+    Weight, generate_latex_graph,
 };
 
 pub trait LatexDisplay {
     fn to_latex(&self) -> String;
+
+    /// Rendering used when the value is the storage of an `UndirectedGraph`. Storages that
+    /// hold every edge as two arcs override it to show each edge once.
+    fn to_latex_undirected(&self) -> String {
+        self.to_latex()
+    }
+
+    /// Packages the output of `to_latex` needs, for `standalone_document`.
+    fn required_packages(&self) -> &[&str] {
+        &["amsmath"]
+    }
 }
 
 pub trait LatexVisualDisplay {
     fn to_latex_visual(&self) -> String;
+
+    /// Packages the output of `to_latex_visual` needs, for `standalone_document`.
+    fn required_packages(&self) -> &[&str] {
+        &["tikz"]
+    }
+}
+
+/// Edge weight as written in graph drawings. Numeric weights use their `Display` output;
+/// the unit weight of unweighted graphs carries no information and gets no label.
+pub trait WeightLabel {
+    fn weight_label(&self) -> Option<String>;
+}
+
+impl WeightLabel for () {
+    fn weight_label(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T> WeightLabel for T
+where
+    T: Weight + Display,
+{
+    fn weight_label(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// Packages needed by the matrices rendered with `LatexMatrix`.
+pub(crate) const MATRIX_PACKAGES: &[&str] = &["amsmath", "nicematrix"];
+
+/// Wraps a fragment returned by `to_latex` or `to_latex_visual` in a minimal document that
+/// compiles with `pdflatex`, loading each of `packages` once. The `article` class is used
+/// since graph drawings are placed in a `figure`.
+pub fn standalone_document(body: &str, packages: &[&str]) -> String {
+    let mut document = String::from("\\documentclass{article}\n");
+    let mut loaded: Vec<&str> = Vec::new();
+    for &package in packages {
+        if !loaded.contains(&package) {
+            loaded.push(package);
+            document.push_str(&format!("\\usepackage{{{}}}\n", package));
+        }
+    }
+    document.push_str("\\pagestyle{empty}\n\\begin{document}\n");
+    document.push_str(body);
+    document.push_str("\n\\end{document}\n");
+    document
+}
+
+/// Visual data for a whole graph, with one edge per logical edge labeled by its weight.
+pub(crate) fn graph_visual_data<G>(graph: &G) -> VisualGraphData
+where
+    G: Graph + EdgeWeights,
+    G::Key: Display,
+    G::W: WeightLabel,
+{
+    let labels = graph
+        .node_ids()
+        .map(|id| graph.node_key(id).to_string())
+        .collect();
+
+    let edges = graph
+        .logical_edge_ids()
+        .into_iter()
+        .map(|eid| {
+            let (u, v) = graph.endpoints(eid);
+            VisualEdge {
+                u: u.0,
+                v: v.0,
+                label: graph.weight_of(eid).and_then(|w| w.weight_label()),
+            }
+        })
+        .collect();
+
+    VisualGraphData::new(labels, edges, graph.is_directed())
 }
 
 pub struct LatexMatrix<'a, T> {
@@ -25,13 +110,53 @@ pub struct LatexMatrix<'a, T> {
     pub format_cell: &'a dyn Fn(&T) -> String,
 }
 
+impl<T> LatexMatrix<'_, T> {
+    /// Checks that the labels match the shape of the data and that all rows have the
+    /// same number of columns. `to_latex` still renders malformed matrices, padding
+    /// missing cells with blanks, but the result is unlikely to be what was intended.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.row_labels.is_empty() && self.row_labels.len() != self.data.len() {
+            return Err(format!(
+                "Matrix has {} rows but {} row labels",
+                self.data.len(),
+                self.row_labels.len()
+            ));
+        }
+
+        let width = self.column_count();
+        if let Some((i, row)) = self.data.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(format!(
+                "Row {} has {} columns, expected {}",
+                i,
+                row.len(),
+                width
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Number of columns: one per column label, or the longest row if there are none.
+    fn column_count(&self) -> usize {
+        if self.col_labels.is_empty() {
+            self.data.iter().map(Vec::len).max().unwrap_or(0)
+        } else {
+            self.col_labels.len()
+        }
+    }
+}
+
 impl<T> LatexDisplay for LatexMatrix<'_, T> {
+    fn required_packages(&self) -> &[&str] {
+        MATRIX_PACKAGES
+    }
+
     fn to_latex(&self) -> String {
-        let mut row_label_indices: Vec<usize> = (0..self.row_labels.len()).collect();
-        row_label_indices.sort_by_key(|&i| &self.row_labels[i]);
+        let mut row_indices: Vec<usize> = (0..self.data.len()).collect();
+        row_indices.sort_by_key(|&i| self.row_labels.get(i));
 
-        let mut col_label_indices: Vec<usize> = (0..self.col_labels.len()).collect();
-        col_label_indices.sort_by_key(|&i| &self.col_labels[i]);
+        let mut col_indices: Vec<usize> = (0..self.column_count()).collect();
+        col_indices.sort_by_key(|&j| self.col_labels.get(j));
 
         let mut latex_string = String::new();
 
@@ -63,15 +188,11 @@ impl<T> LatexDisplay for LatexMatrix<'_, T> {
                 latex_string.push_str("    & ");
             }
 
-            for (idx, &i) in col_label_indices.iter().enumerate() {
-                let label = &self.col_labels[i];
-                latex_string.push_str(label);
-
-                // Add separator unless it's the last column label
-                if idx < self.col_labels.len() - 1 {
-                    latex_string.push_str(" & ");
-                }
-            }
+            let labels: Vec<&str> = col_indices
+                .iter()
+                .map(|&j| self.col_labels[j].as_str())
+                .collect();
+            latex_string.push_str(&labels.join(" & "));
 
             // Empty cell for the top-right corner (above last L_1)
             if has_row_labels {
@@ -81,26 +202,22 @@ impl<T> LatexDisplay for LatexMatrix<'_, T> {
             latex_string.push_str(" \\\\\n");
         }
 
-        // 4. Generate Body Rows (Left Label -> Data)
-        for i in row_label_indices {
+        // 4. Generate Body Rows (Left Label -> Data), padding missing cells
+        for i in row_indices {
             let row = &self.data[i];
 
             // Left Row Label
             if has_row_labels {
-                if let Some(label) = self.row_labels.get(i) {
-                    latex_string.push_str(label);
-                    latex_string.push_str(" & ");
-                }
+                latex_string.push_str(self.row_labels.get(i).map_or("", String::as_str));
+                latex_string.push_str(" & ");
             }
 
             // Data Cells
-            for (idx, &j) in col_label_indices.iter().enumerate() {
-                let cell = &row[j];
-                latex_string.push_str(&(self.format_cell)(cell));
-                if idx < row.len() - 1 {
-                    latex_string.push_str(" & ");
-                }
-            }
+            let cells: Vec<String> = col_indices
+                .iter()
+                .map(|&j| row.get(j).map(self.format_cell).unwrap_or_default())
+                .collect();
+            latex_string.push_str(&cells.join(" & "));
 
             latex_string.push_str(" \\\\\n");
         }
@@ -146,90 +263,170 @@ where
     K: Clone + Eq + std::hash::Hash,
 {
     fn to_latex(&self) -> String {
-        self.storage.to_latex()
+        self.storage.to_latex_undirected()
     }
 }
 
-impl<K, D, E, W> LatexDisplay for GraphDefinition<K, D, E, W>
+impl<K, D, E, W> GraphDefinition<K, D, E, W>
 where
-    K: Debug + Clone + Eq + Hash + Display,
+    K: Debug + Clone + Eq + Hash + Display + Ord,
     D: Debug + Clone,
     E: Debug + Clone,
     W: Debug + Copy + PartialOrd,
 {
-    fn to_latex(&self) -> String {
-        let definition_string = format!(
-            "G = (V, E) with |V| = {} and |E| = {}",
-            self.order(),
-            self.size()
-        );
-
+    /// Set notation with nodes and edges in key order (`edges_sorted`). Undirected edges are
+    /// stored as two opposite arcs (self-loops included), so each pair is written once as
+    /// `(a, b)` with `a <= b`.
+    fn set_notation(&self, undirected: bool) -> String {
         let mut nodes = self
             .nodes
             .iter()
-            .map(|(_, record)| record.key.to_string())
+            .map(|(_, record)| &record.key)
             .collect::<Vec<_>>();
 
         nodes.sort();
 
-        let nodes_string = format!("V = {{ {} }}", nodes.join(", "));
+        let mut endpoints = self
+            .edges_sorted()
+            .into_iter()
+            .map(|e| {
+                let (from, to) = self.endpoints(e);
+                (self.node_key(from), self.node_key(to))
+            })
+            .collect::<Vec<_>>();
+
+        if undirected {
+            // Turning every arc to `a <= b` puts both arcs of an edge next to each other
+            endpoints = endpoints
+                .into_iter()
+                .map(|(from, to)| if to < from { (to, from) } else { (from, to) })
+                .collect();
+            endpoints.sort();
+            endpoints = endpoints.into_iter().step_by(2).collect();
+        }
 
-        let mut edges = self
-            .edges
+        let edges = endpoints
             .iter()
-            .map(|edge| {
-                let from_key = &self.nodes.get(edge.from).key;
-                let to_key = &self.nodes.get(edge.to).key;
-                format!("({}, {})", from_key, to_key)
-            })
+            .map(|(from, to)| format!("({}, {})", from, to))
             .collect::<Vec<_>>();
+        let nodes = nodes.iter().map(|key| key.to_string()).collect::<Vec<_>>();
 
+        let definition_string = format!(
+            "G = (V, E) with |V| = {} and |E| = {}",
+            self.order(),
+            edges.len()
+        );
+        let nodes_string = format!("V = {{ {} }}", nodes.join(", "));
         let edges_string = format!("E = {{ {} }}", edges.join(", "));
 
-        edges.sort();
-
         definition_string + "\n" + &nodes_string + "\n" + &edges_string
     }
 }
 
-impl<K, D, E, W> LatexDisplay for AdjacencyMatrix<K, D, E, W>
+impl<K, D, E, W> LatexDisplay for GraphDefinition<K, D, E, W>
+where
+    K: Debug + Clone + Eq + Hash + Display + Ord,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn to_latex(&self) -> String {
+        self.set_notation(false)
+    }
+
+    fn to_latex_undirected(&self) -> String {
+        self.set_notation(true)
+    }
+}
+
+impl<K, D, E, W> AdjacencyMatrix<K, D, E, W>
 where
     K: Debug + Clone + Eq + Hash + Default,
     D: Debug + Clone + Default,
     E: Debug + Clone + Default,
     W: Debug + Copy + PartialOrd + LatexDisplay,
 {
-    fn to_latex(&self) -> String {
+    /// Renders the matrix in inline math with `present` in the cells of edges that have
+    /// no weight and `absent` in the cells without an edge. Weighted edges show their weight.
+    pub fn to_latex_with_markers(&self, present: &str, absent: &str) -> String {
         let mut latex_string = String::new();
 
-        latex_string.push_str("\\begin{pmatrix}\n");
+        latex_string.push_str("$\\begin{pmatrix}\n");
         for i in 0..self.n {
-            let mut row_entries = Vec::new();
-            for j in 0..self.n {
-                let edge = self.get_edge_id(NodeId(i), NodeId(j));
-
-                let weight = match edge {
-                    Some(eid) => match self.weight_of(eid) {
-                        Some(w) => &w.to_latex(),
-                        None => panic!("Edge weight should be defined for existing edges"),
-                    },
-                    None => "∞",
-                };
-                row_entries.push(format!("{}", weight));
-            }
+            let row_entries: Vec<String> = (0..self.n)
+                .map(|j| match self.get_edge_id(NodeId(i), NodeId(j)) {
+                    Some(eid) => self
+                        .weight_of(eid)
+                        .map_or_else(|| present.to_string(), |w| w.to_latex()),
+                    None => absent.to_string(),
+                })
+                .collect();
             latex_string.push_str(&row_entries.join(" & "));
             if i < self.n - 1 {
                 latex_string.push_str(" \\\\\n");
             } else {
-                latex_string.push_str("\n");
+                latex_string.push('\n');
             }
         }
-        latex_string.push_str("\\end{pmatrix}");
+        latex_string.push_str("\\end{pmatrix}$");
 
         latex_string
     }
 }
 
+impl<K, D, E, W> LatexDisplay for AdjacencyMatrix<K, D, E, W>
+where
+    K: Debug + Clone + Eq + Hash + Default,
+    D: Debug + Clone + Default,
+    E: Debug + Clone + Default,
+    W: Debug + Copy + PartialOrd + LatexDisplay,
+{
+    /// Unweighted edges are marked with `1` and missing edges with `\infty`.
+    fn to_latex(&self) -> String {
+        self.to_latex_with_markers("1", "\\infty")
+    }
+}
+
+/// Visual data with one edge per stored edge id, labeled by `label`.
+fn labeled_visual_data<G>(graph: &G, label: impl Fn(EdgeId) -> Option<String>) -> VisualGraphData
+where
+    G: Graph,
+    G::Key: Display,
+{
+    let labels: Vec<String> = graph
+        .node_ids()
+        .map(|id| graph.node_key(id).to_string())
+        .collect();
+
+    let mut edges = Vec::new();
+    for eid in graph.edge_ids() {
+        let (u, v) = graph.endpoints(eid);
+        edges.push(VisualEdge {
+            u: u.0,
+            v: v.0,
+            label: label(eid),
+        });
+    }
+
+    VisualGraphData::new(labels, edges, graph.is_directed())
+}
+
+impl<S, GK, K, D, E, W> DirectedGraph<S, GK, K, D, E, W>
+where
+    S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash + Display,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    /// Like `to_latex_visual`, but every edge is labeled by `label` (e.g. with its metadata
+    /// or id) instead of its weight. `None` leaves the edge unlabeled.
+    pub fn to_latex_visual_with(&self, label: impl Fn(EdgeId) -> Option<String>) -> String {
+        generate_latex_graph(labeled_visual_data(self, label))
+    }
+}
+
 impl<S, GK, K, D, E, W> LatexVisualDisplay for DirectedGraph<S, GK, K, D, E, W>
 where
     DirectedGraph<S, GK, K, D, E, W>: EdgeWeights<W = W>,
@@ -238,32 +435,26 @@ where
     K: Debug + Clone + Eq + Hash + Display,
     D: Debug + Clone,
     E: Debug + Clone,
-    W: Debug + Copy + PartialOrd,
+    W: Debug + Copy + PartialOrd + WeightLabel,
 {
     fn to_latex_visual(&self) -> String {
-        let n = self.order();
-        let labels: Vec<String> = (0..n)
-            .map(|i| self.node_key(NodeId(i)).to_string())
-            .collect();
-
-        let mut edges = Vec::new();
-        for eid in self.storage.edge_ids() {
-            let (u, v) = self.endpoints(eid);
-            let label = self.weight_of(eid).map(|w| format!("{:?}", w));
-            edges.push(VisualEdge {
-                u: u.0,
-                v: v.0,
-                label,
-            });
-        }
-
-        let data = VisualGraphData {
-            labels,
-            edges,
-            is_directed: true,
-        };
+        self.to_latex_visual_with(|eid| self.weight_of(eid).and_then(|w| w.weight_label()))
+    }
+}
 
-        generate_latex_graph(data)
+impl<S, GK, K, D, E, W> UndirectedGraph<S, GK, K, D, E, W>
+where
+    S: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash + Display,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    /// Like `to_latex_visual`, but every edge is labeled by `label` (e.g. with its metadata
+    /// or id) instead of its weight. `None` leaves the edge unlabeled.
+    pub fn to_latex_visual_with(&self, label: impl Fn(EdgeId) -> Option<String>) -> String {
+        generate_latex_graph(labeled_visual_data(self, label))
     }
 }
 
@@ -275,31 +466,108 @@ where
     K: Debug + Clone + Eq + Hash + Display,
     D: Debug + Clone,
     E: Debug + Clone,
-    W: Debug + Copy + PartialOrd,
+    W: Debug + Copy + PartialOrd + WeightLabel,
 {
     fn to_latex_visual(&self) -> String {
-        let n = self.order();
-        let labels: Vec<String> = (0..n)
-            .map(|i| self.node_key(NodeId(i)).to_string())
-            .collect();
-
-        let mut edges = Vec::new();
-        for eid in self.storage.edge_ids() {
-            let (u, v) = self.endpoints(eid);
-            let label = self.weight_of(eid).map(|w| format!("{:?}", w));
-            edges.push(VisualEdge {
-                u: u.0,
-                v: v.0,
-                label,
-            });
-        }
+        self.to_latex_visual_with(|eid| self.weight_of(eid).and_then(|w| w.weight_label()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DirectedGraph, Simple};
+
+    const TRIANGLE: [(&str, &str); 3] = [("a", "b"), ("c", "b"), ("a", "c")];
+
+    #[test]
+    fn undirected_set_notation_writes_each_edge_once() {
+        let triangle =
+            UndirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges(TRIANGLE);
+        assert_eq!(
+            triangle.to_latex(),
+            "G = (V, E) with |V| = 3 and |E| = 3\n\
+             V = { a, b, c }\n\
+             E = { (a, b), (a, c), (b, c) }"
+        );
+    }
+
+    #[test]
+    fn directed_set_notation_keeps_arc_directions() {
+        let triangle =
+            DirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges(TRIANGLE);
+        assert_eq!(
+            triangle.to_latex(),
+            "G = (V, E) with |V| = 3 and |E| = 3\n\
+             V = { a, b, c }\n\
+             E = { (a, b), (a, c), (c, b) }"
+        );
+    }
+
+    #[test]
+    fn set_notation_follows_key_order() {
+        let path = UndirectedGraph::<GraphDefinition<usize>, Simple, usize>::from_edges([
+            (10usize, 2usize),
+            (2, 1),
+        ]);
+        assert_eq!(
+            path.to_latex(),
+            "G = (V, E) with |V| = 3 and |E| = 2\n\
+             V = { 1, 2, 10 }\n\
+             E = { (1, 2), (2, 10) }"
+        );
+    }
 
-        let data = VisualGraphData {
-            labels,
-            edges,
-            is_directed: false,
+    #[test]
+    fn jagged_matrix_rows_are_padded() {
+        let data = vec![vec![1, 2, 3], vec![4]];
+        let format_cell = |x: &i32| x.to_string();
+        let matrix = LatexMatrix {
+            row_labels: vec!["a".to_string(), "b".to_string()],
+            col_labels: vec!["x".to_string(), "y".to_string(), "z".to_string()],
+            data: &data,
+            format_cell: &format_cell,
         };
+        assert_eq!(
+            matrix.validate(),
+            Err("Row 1 has 1 columns, expected 3".to_string())
+        );
+        let expected = [
+            "$\\begin{pNiceMatrix}[first-row,first-col]",
+            "    & x & y & z &  \\\\",
+            "a & 1 & 2 & 3 \\\\",
+            "b & 4 &  &  \\\\",
+            "\\end{pNiceMatrix}$",
+        ];
+        assert_eq!(matrix.to_latex(), expected.join("\n"));
+    }
 
-        generate_latex_graph(data)
+    #[test]
+    fn empty_matrix_is_an_empty_environment() {
+        let data: Vec<Vec<i32>> = Vec::new();
+        let format_cell = |x: &i32| x.to_string();
+        let matrix = LatexMatrix {
+            row_labels: Vec::new(),
+            col_labels: Vec::new(),
+            data: &data,
+            format_cell: &format_cell,
+        };
+        assert_eq!(matrix.validate(), Ok(()));
+        assert_eq!(
+            matrix.to_latex(),
+            "$\\begin{pNiceMatrix}\n\\end{pNiceMatrix}$"
+        );
+
+        // Rows without columns render as empty rows
+        let data: Vec<Vec<i32>> = vec![Vec::new(), Vec::new()];
+        let matrix = LatexMatrix {
+            data: &data,
+            ..matrix
+        };
+        assert_eq!(matrix.validate(), Ok(()));
+        assert_eq!(
+            matrix.to_latex(),
+            "$\\begin{pNiceMatrix}\n \\\\\n \\\\\n\\end{pNiceMatrix}$"
+        );
     }
 }
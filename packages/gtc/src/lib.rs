@@ -1,17 +1,21 @@
 pub mod algorithms;
 pub mod core;
+pub mod generators;
 pub mod interner;
 pub mod latex;
 pub mod storage;
 pub mod traits;
+pub mod util;
 pub mod visual;
 pub mod wrappers;
 
 pub use algorithms::*;
 pub use core::*;
+pub use generators::*;
 pub use interner::*;
 pub use latex::*;
 pub use storage::*;
 pub use traits::*;
+pub use util::*;
 pub use visual::*;
 pub use wrappers::*;
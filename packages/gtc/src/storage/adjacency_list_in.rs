@@ -4,7 +4,8 @@ use crate::core::{EdgeId, NodeId};
 use crate::interner::NodeInterner;
 use crate::storage::graph_definition::{EdgeRecord as GEdgeRecord, GraphDefinition};
 use crate::traits::{
-    EdgeWeights, GraphBase, MutableStorage, StorageConvert, StorageRepresentation,
+    EdgeWeights, GraphBase, MutableNodeData, MutableStorage, MutableWeights, StorageConvert,
+    StorageRepresentation,
 };
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -301,6 +302,41 @@ where
     }
 }
 
+impl<Key, Data, EdgeMeta, Weight> MutableWeights for AdjacencyListIn<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn set_weight(&mut self, e: EdgeId, weight: Option<Self::Weight>) {
+        self.edges[e.0].weight = weight;
+    }
+
+    fn edge_meta_mut(&mut self, e: EdgeId) -> &mut Self::EdgeMeta {
+        &mut self.edges[e.0].meta
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableNodeData for AdjacencyListIn<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn add_node_or_update(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        let id = self.nodes.intern_or_update(key, data);
+        if self.out_adj.len() <= id.0 {
+            self.out_adj.resize(id.0 + 1, Vec::new());
+        }
+        if self.in_adj.len() <= id.0 {
+            self.in_adj.resize(id.0 + 1, Vec::new());
+        }
+        id
+    }
+}
+
 impl<K, D, E, W, Target> StorageConvert<Target> for AdjacencyListIn<K, D, E, W>
 where
     Target: From<GraphDefinition<K, D, E, W>>,
@@ -1,16 +1,21 @@
 //! Dense adjacency matrix stored as flat Vec<Option<EdgeId>> referencing edges Vec.
 //! Edges Vec keeps edge records (including weight & meta). This way the matrix is a presence map of edges.
 //! This matches the proposed design to avoid storing both weight and meta in the matrix cells.
+//! A cell references the first edge of its ordered pair; further parallel edges are kept in a
+//! side map so multigraphs keep every edge reachable through `edges_between`.
 
 use indexmap::IndexSet;
 
 use crate::core::{EdgeId, NodeId};
 use crate::interner::NodeInterner;
+use crate::latex::WeightLabel;
 use crate::storage::graph_definition::{EdgeRecord as GEdgeRecord, GraphDefinition};
 use crate::traits::{
-    EdgeWeights, GraphBase, MutableStorage, StorageConvert, StorageRepresentation,
+    EdgeWeights, GraphBase, MutableNodeData, MutableStorage, MutableWeights, StorageConvert,
+    StorageRepresentation,
 };
-use std::fmt::Debug;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 
 pub type EdgeRecord<EdgeMeta, Weight> = GEdgeRecord<EdgeMeta, Weight>;
@@ -27,6 +32,8 @@ where
     pub nodes: NodeInterner<Key, Data>,
     pub edges: Vec<EdgeRecord<EdgeMeta, Weight>>,
     pub data: Vec<Option<EdgeId>>,
+    /// Edges after the first between the same ordered pair, in insertion order.
+    pub parallel: HashMap<(NodeId, NodeId), Vec<EdgeId>>,
 }
 
 impl<Key, Data, EdgeMeta, Weight> StorageRepresentation
@@ -55,6 +62,7 @@ where
             nodes: NodeInterner::new(),
             edges: Vec::new(),
             data: vec![None; n * n],
+            parallel: HashMap::new(),
         }
     }
 
@@ -63,6 +71,29 @@ where
         r * self.n + c
     }
 
+    /// Widens the matrix to `n` nodes, moving every row to its new offset.
+    fn grow(&mut self, n: usize) {
+        if n <= self.n {
+            return;
+        }
+        let mut data = vec![None; n * n];
+        for r in 0..self.n {
+            data[r * n..r * n + self.n].copy_from_slice(&self.data[r * self.n..(r + 1) * self.n]);
+        }
+        self.data = data;
+        self.n = n;
+    }
+
+    /// Records `eid` in its cell, or among the parallel edges if the cell is taken.
+    fn index_edge(&mut self, from: NodeId, to: NodeId, eid: EdgeId) {
+        self.grow(self.nodes.len().max(from.0 + 1).max(to.0 + 1));
+        let i = self.idx(from.0, to.0);
+        match self.data[i] {
+            None => self.data[i] = Some(eid),
+            Some(_) => self.parallel.entry((from, to)).or_default().push(eid),
+        }
+    }
+
     pub fn from_graphdef(def: GraphDefinition<Key, Data, EdgeMeta, Weight>) -> Self {
         let (records, index) = def.nodes.into_parts();
         let mut nodes = NodeInterner::new();
@@ -74,12 +105,12 @@ where
             nodes,
             edges: Vec::new(),
             data: vec![None; n * n],
+            parallel: HashMap::new(),
         };
         for er in def.edges.into_iter() {
             let eid = EdgeId(mat.edges.len());
-            let i = mat.idx(er.from.0, er.to.0);
+            mat.index_edge(er.from, er.to, eid);
             mat.edges.push(er);
-            mat.data[i] = Some(eid);
         }
         mat
     }
@@ -94,16 +125,11 @@ where
         let eid = EdgeId(self.edges.len());
         self.edges
             .push(EdgeRecord::new(from, to, meta.clone(), weight));
-        let i = self.idx(from.0, to.0);
-        if self.data.len() <= i {
-            let newn = self.nodes.len();
-            self.data.resize(newn * newn, None);
-            self.n = newn;
-        }
-        self.data[i] = Some(eid);
+        self.index_edge(from, to, eid);
         eid
     }
 
+    /// The first edge from `from` to `to`; `edges_between` also yields parallel edges.
     pub fn get_edge_id(&self, from: NodeId, to: NodeId) -> Option<EdgeId> {
         self.data[self.idx(from.0, to.0)]
     }
@@ -178,12 +204,18 @@ where
         &self.edges[e.0].meta
     }
     fn edges_between(&self, from: NodeId, to: NodeId) -> Box<dyn Iterator<Item = EdgeId> + '_> {
-        let i = self.idx(from.0, to.0);
-        if let Some(eid) = self.data[i] {
-            Box::new(std::iter::once(eid))
-        } else {
-            Box::new(std::iter::empty())
+        if from.0 >= self.n || to.0 >= self.n {
+            return Box::new(std::iter::empty());
         }
+
+        let first = self.data[self.idx(from.0, to.0)];
+        let rest = self
+            .parallel
+            .get(&(from, to))
+            .into_iter()
+            .flatten()
+            .copied();
+        Box::new(first.into_iter().chain(rest))
     }
 
     fn neighborhood(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
@@ -213,14 +245,9 @@ where
             return Box::new(std::iter::empty());
         }
 
-        let predecessors = (0..self.n).filter_map(move |u| {
-            let i = self.idx(u, v.0);
-            if let Some(eid) = self.data[i] {
-                Some(self.edges[eid.0].from)
-            } else {
-                None
-            }
-        });
+        // One entry per edge, so parallel edges repeat their endpoint.
+        let predecessors =
+            (0..self.n).flat_map(move |u| self.edges_between(NodeId(u), v).map(move |_| NodeId(u)));
         Box::new(predecessors)
     }
     fn successors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
@@ -228,14 +255,9 @@ where
             return Box::new(std::iter::empty());
         }
 
-        let successors = (0..self.n).filter_map(move |u| {
-            let i = self.idx(v.0, u);
-            if let Some(eid) = self.data[i] {
-                Some(self.edges[eid.0].to)
-            } else {
-                None
-            }
-        });
+        // One entry per edge, so parallel edges repeat their endpoint.
+        let successors =
+            (0..self.n).flat_map(move |u| self.edges_between(v, NodeId(u)).map(move |_| NodeId(u)));
         Box::new(successors)
     }
 }
@@ -262,11 +284,7 @@ where
 {
     fn add_node(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
         let id = self.nodes.intern(key, data);
-        let n_new = self.nodes.len();
-        if n_new > self.n {
-            self.data.resize(n_new * n_new, None);
-            self.n = n_new;
-        }
+        self.grow(self.nodes.len());
         id
     }
 
@@ -278,14 +296,8 @@ where
         weight: Option<Self::Weight>,
     ) -> EdgeId {
         let eid = EdgeId(self.edges.len());
-        if self.n <= from.0 || self.n <= to.0 {
-            let newn = self.nodes.len();
-            self.data.resize(newn * newn, None);
-            self.n = newn;
-        }
         self.edges.push(EdgeRecord::new(from, to, meta, weight));
-        let idx = self.idx(from.0, to.0);
-        self.data[idx] = Some(eid);
+        self.index_edge(from, to, eid);
         eid
     }
 
@@ -308,6 +320,37 @@ where
         for i in 0..self.data.len() {
             self.data[i] = None;
         }
+        self.parallel.clear();
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableWeights for AdjacencyMatrix<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn set_weight(&mut self, e: EdgeId, weight: Option<Self::Weight>) {
+        self.edges[e.0].weight = weight;
+    }
+
+    fn edge_meta_mut(&mut self, e: EdgeId) -> &mut Self::EdgeMeta {
+        &mut self.edges[e.0].meta
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableNodeData for AdjacencyMatrix<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn add_node_or_update(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        let id = self.nodes.intern_or_update(key, data);
+        self.grow(self.nodes.len());
+        id
     }
 }
 
@@ -331,3 +374,66 @@ where
         Target::from(def)
     }
 }
+
+/// Plain-text adjacency matrix with node keys as row and column headers. Cells hold the
+/// edge weight, `1` for an edge without a weight and `.` for no edge, right-aligned to the
+/// widest cell.
+impl<Key, Data, EdgeMeta, Weight> Display for AdjacencyMatrix<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Display,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd + WeightLabel,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = self.nodes.len();
+        let keys: Vec<String> = self
+            .nodes
+            .records
+            .iter()
+            .map(|r| r.key.to_string())
+            .collect();
+
+        let cells: Vec<Vec<String>> = (0..order)
+            .map(|i| {
+                (0..order)
+                    .map(|j| {
+                        let cell = (i < self.n && j < self.n)
+                            .then(|| self.data[i * self.n + j])
+                            .flatten();
+                        match cell {
+                            Some(eid) => self.edges[eid.0]
+                                .weight
+                                .and_then(|w| w.weight_label())
+                                .unwrap_or_else(|| "1".to_string()),
+                            None => ".".to_string(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let width = keys
+            .iter()
+            .chain(cells.iter().flatten())
+            .map(|s| s.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:>width$}", "")?;
+        for key in &keys {
+            write!(f, " {:>width$}", key)?;
+        }
+        writeln!(f)?;
+
+        for (key, row) in keys.iter().zip(&cells) {
+            write!(f, "{:>width$}", key)?;
+            for cell in row {
+                write!(f, " {:>width$}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+//! AutoStorage: an adjacency list or an adjacency matrix, picked at runtime from a
+//! `RepresentationHint`. Every storage trait is forwarded to the chosen backend, so it plugs
+//! into the wrappers like any other storage.
+
+use crate::core::{EdgeId, NodeId, RepresentationHint};
+use crate::storage::{AdjacencyList, AdjacencyMatrix, GraphDefinition};
+use crate::traits::{
+    EdgeWeights, GraphBase, MutableNodeData, MutableStorage, MutableWeights, StorageConvert,
+    StorageRepresentation,
+};
+use crate::wrappers::{DirectedGraph, Pseudo};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Largest order for which `Auto` (and `PreferAdjMatrix`) pick a matrix; it has `n^2` cells.
+pub const AUTO_MATRIX_MAX_ORDER: usize = 1024;
+
+/// Smallest density (edges over `n^2` cells) for which `Auto` picks a matrix.
+pub const AUTO_MATRIX_MIN_DENSITY: f64 = 0.25;
+
+#[derive(Clone)]
+pub enum AutoStorage<Key = String, Data = (), EdgeMeta = (), Weight = ()>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    List(AdjacencyList<Key, Data, EdgeMeta, Weight>),
+    Matrix(AdjacencyMatrix<Key, Data, EdgeMeta, Weight>),
+}
+
+impl RepresentationHint {
+    /// Whether a graph with `order` nodes and `size` edges should be stored as a matrix.
+    ///
+    /// - `ForceAdjList` / `ForceAdjMatrix`: always the named storage.
+    /// - `PreferAdjList`: always a list, since a list never has a prohibitive cost.
+    /// - `PreferAdjMatrix`: a matrix unless `order` exceeds `AUTO_MATRIX_MAX_ORDER`.
+    /// - `Auto`: a matrix if `order` is at most `AUTO_MATRIX_MAX_ORDER` and the density
+    ///   `size / order^2` is at least `AUTO_MATRIX_MIN_DENSITY`, a list otherwise.
+    pub fn prefers_matrix(self, order: usize, size: usize) -> bool {
+        match self {
+            RepresentationHint::ForceAdjList | RepresentationHint::PreferAdjList => false,
+            RepresentationHint::ForceAdjMatrix => true,
+            RepresentationHint::PreferAdjMatrix => order <= AUTO_MATRIX_MAX_ORDER,
+            RepresentationHint::Auto => {
+                order > 0
+                    && order <= AUTO_MATRIX_MAX_ORDER
+                    && size as f64 / (order * order) as f64 >= AUTO_MATRIX_MIN_DENSITY
+            }
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    /// Stores `def` in the backend chosen by `hint` for its order and size.
+    pub fn from_graphdef(
+        hint: RepresentationHint,
+        def: GraphDefinition<Key, Data, EdgeMeta, Weight>,
+    ) -> Self {
+        if hint.prefers_matrix(def.order(), def.size()) {
+            AutoStorage::Matrix(AdjacencyMatrix::from_graphdef(def))
+        } else {
+            AutoStorage::List(AdjacencyList::from_graphdef(def))
+        }
+    }
+
+    pub fn is_matrix(&self) -> bool {
+        matches!(self, AutoStorage::Matrix(_))
+    }
+}
+
+/// Builds a directed graph whose storage is picked by `hint` (see
+/// `RepresentationHint::prefers_matrix`). Nodes first appearing in `edges` get default data.
+/// Edges are stored as given, hence the `Pseudo` kind.
+pub fn build_graph<K, D, E, W, NI, EI>(
+    hint: RepresentationHint,
+    nodes: NI,
+    edges: EI,
+) -> DirectedGraph<AutoStorage<K, D, E, W>, Pseudo, K, D, E, W>
+where
+    K: Debug + Clone + Eq + Hash + Default,
+    D: Debug + Clone + Default,
+    E: Debug + Clone + Default,
+    W: Debug + Copy + PartialOrd + Default,
+    NI: IntoIterator<Item = (K, D)>,
+    EI: IntoIterator<Item = (K, K, E, Option<W>)>,
+{
+    let mut def = GraphDefinition::new();
+    for (key, data) in nodes {
+        def.add_node(key, data);
+    }
+    for (from, to, meta, weight) in edges {
+        def.add_edge_by_key(from, to, D::default(), D::default(), meta, weight);
+    }
+
+    DirectedGraph::new(AutoStorage::from_graphdef(hint, def))
+}
+
+impl<Key, Data, EdgeMeta, Weight> StorageRepresentation for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    /// Starts as a list, which is cheap to grow.
+    fn with_node_capacity(capacity: usize) -> Self {
+        AutoStorage::List(AdjacencyList::with_node_capacity(capacity))
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> From<GraphDefinition<Key, Data, EdgeMeta, Weight>>
+    for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn from(def: GraphDefinition<Key, Data, EdgeMeta, Weight>) -> Self {
+        Self::from_graphdef(RepresentationHint::Auto, def)
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> GraphBase for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    type Key = Key;
+    type Data = Data;
+    type EdgeMeta = EdgeMeta;
+    type Weight = Weight;
+
+    fn order(&self) -> usize {
+        match self {
+            AutoStorage::List(s) => s.order(),
+            AutoStorage::Matrix(s) => s.order(),
+        }
+    }
+    fn size(&self) -> usize {
+        match self {
+            AutoStorage::List(s) => s.size(),
+            AutoStorage::Matrix(s) => s.size(),
+        }
+    }
+
+    fn node_id(&self, key: &Self::Key) -> Option<NodeId> {
+        match self {
+            AutoStorage::List(s) => s.node_id(key),
+            AutoStorage::Matrix(s) => s.node_id(key),
+        }
+    }
+    fn node_ids(&self) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        match self {
+            AutoStorage::List(s) => s.node_ids(),
+            AutoStorage::Matrix(s) => s.node_ids(),
+        }
+    }
+    fn node_key(&self, id: NodeId) -> &Self::Key {
+        match self {
+            AutoStorage::List(s) => s.node_key(id),
+            AutoStorage::Matrix(s) => s.node_key(id),
+        }
+    }
+    fn node_data(&self, id: NodeId) -> &Self::Data {
+        match self {
+            AutoStorage::List(s) => s.node_data(id),
+            AutoStorage::Matrix(s) => s.node_data(id),
+        }
+    }
+
+    fn edge_ids(&self) -> Box<dyn Iterator<Item = EdgeId> + '_> {
+        match self {
+            AutoStorage::List(s) => s.edge_ids(),
+            AutoStorage::Matrix(s) => s.edge_ids(),
+        }
+    }
+    fn endpoints(&self, e: EdgeId) -> (NodeId, NodeId) {
+        match self {
+            AutoStorage::List(s) => s.endpoints(e),
+            AutoStorage::Matrix(s) => s.endpoints(e),
+        }
+    }
+    fn edge_meta(&self, e: EdgeId) -> &Self::EdgeMeta {
+        match self {
+            AutoStorage::List(s) => s.edge_meta(e),
+            AutoStorage::Matrix(s) => s.edge_meta(e),
+        }
+    }
+    fn edges_between(&self, from: NodeId, to: NodeId) -> Box<dyn Iterator<Item = EdgeId> + '_> {
+        match self {
+            AutoStorage::List(s) => s.edges_between(from, to),
+            AutoStorage::Matrix(s) => s.edges_between(from, to),
+        }
+    }
+
+    fn neighborhood(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        match self {
+            AutoStorage::List(s) => s.neighborhood(v),
+            AutoStorage::Matrix(s) => s.neighborhood(v),
+        }
+    }
+    fn successors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        match self {
+            AutoStorage::List(s) => s.successors(v),
+            AutoStorage::Matrix(s) => s.successors(v),
+        }
+    }
+    fn predecessors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        match self {
+            AutoStorage::List(s) => s.predecessors(v),
+            AutoStorage::Matrix(s) => s.predecessors(v),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> EdgeWeights for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    type W = Weight;
+    fn weight_of(&self, e: EdgeId) -> Option<Self::W> {
+        match self {
+            AutoStorage::List(s) => s.weight_of(e),
+            AutoStorage::Matrix(s) => s.weight_of(e),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableStorage for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn add_node(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        match self {
+            AutoStorage::List(s) => s.add_node(key, data),
+            AutoStorage::Matrix(s) => s.add_node(key, data),
+        }
+    }
+
+    fn add_edge_by_id(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        meta: Self::EdgeMeta,
+        weight: Option<Self::Weight>,
+    ) -> EdgeId {
+        match self {
+            AutoStorage::List(s) => s.add_edge_by_id(from, to, meta, weight),
+            AutoStorage::Matrix(s) => s.add_edge_by_id(from, to, meta, weight),
+        }
+    }
+
+    fn add_edge_by_key(
+        &mut self,
+        from_key: Self::Key,
+        to_key: Self::Key,
+        from_data: Self::Data,
+        to_data: Self::Data,
+        meta: Self::EdgeMeta,
+        weight: Option<Self::Weight>,
+    ) -> EdgeId {
+        match self {
+            AutoStorage::List(s) => {
+                s.add_edge_by_key(from_key, to_key, from_data, to_data, meta, weight)
+            }
+            AutoStorage::Matrix(s) => {
+                s.add_edge_by_key(from_key, to_key, from_data, to_data, meta, weight)
+            }
+        }
+    }
+
+    fn clear_edges(&mut self) {
+        match self {
+            AutoStorage::List(s) => s.clear_edges(),
+            AutoStorage::Matrix(s) => s.clear_edges(),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableWeights for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn set_weight(&mut self, e: EdgeId, weight: Option<Self::Weight>) {
+        match self {
+            AutoStorage::List(s) => s.set_weight(e, weight),
+            AutoStorage::Matrix(s) => s.set_weight(e, weight),
+        }
+    }
+
+    fn edge_meta_mut(&mut self, e: EdgeId) -> &mut Self::EdgeMeta {
+        match self {
+            AutoStorage::List(s) => s.edge_meta_mut(e),
+            AutoStorage::Matrix(s) => s.edge_meta_mut(e),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableNodeData for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn add_node_or_update(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        match self {
+            AutoStorage::List(s) => s.add_node_or_update(key, data),
+            AutoStorage::Matrix(s) => s.add_node_or_update(key, data),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight, Target> StorageConvert<Target>
+    for AutoStorage<Key, Data, EdgeMeta, Weight>
+where
+    Target: From<GraphDefinition<Key, Data, EdgeMeta, Weight>>,
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn convert(&self) -> Target {
+        match self {
+            AutoStorage::List(s) => s.convert(),
+            AutoStorage::Matrix(s) => s.convert(),
+        }
+    }
+}
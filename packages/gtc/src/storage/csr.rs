@@ -0,0 +1,299 @@
+//! CsrStorage: compressed sparse row layout for large static graphs. Out-edges of every node
+//! sit contiguously, sorted by target, and are found by slicing `row_offsets`. In-edges are
+//! indexed the same way in compressed sparse column form (`col_offsets`, `row_indices`), so
+//! predecessors are as cheap as successors. There is no MutableStorage impl; build a graph in
+//! a mutable storage and convert it into CSR instead.
+
+use crate::core::{EdgeId, NodeId};
+use crate::interner::NodeInterner;
+use crate::storage::graph_definition::{EdgeRecord, GraphDefinition};
+use crate::traits::{EdgeWeights, GraphBase, StorageConvert, StorageRepresentation};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Range;
+
+#[derive(Clone)]
+pub struct CsrStorage<Key = String, Data = (), EdgeMeta = (), Weight = ()>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    pub nodes: NodeInterner<Key, Data>,
+    /// Out-edges of node `v` are the positions `row_offsets[v]..row_offsets[v + 1]`.
+    pub row_offsets: Vec<usize>,
+    /// Target of the edge at every position, sorted within each row.
+    pub col_indices: Vec<NodeId>,
+    /// In-edges of node `v` are the positions `col_offsets[v]..col_offsets[v + 1]`.
+    pub col_offsets: Vec<usize>,
+    /// Source of every in-edge, sorted within each column.
+    pub row_indices: Vec<NodeId>,
+    /// Metadata and weight of the edge at every position, whose index is its `EdgeId`.
+    pub meta: Vec<EdgeMeta>,
+    pub weights: Vec<Option<Weight>>,
+}
+
+impl<Key, Data, EdgeMeta, Weight> StorageRepresentation for CsrStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn with_node_capacity(_capacity: usize) -> Self {
+        Self {
+            nodes: NodeInterner::new(),
+            row_offsets: vec![0],
+            col_indices: Vec::new(),
+            col_offsets: vec![0],
+            row_indices: Vec::new(),
+            meta: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> CsrStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    /// Freezes a definition into CSR. Edges are reordered by source and then target (keeping
+    /// insertion order among parallel edges), so their `EdgeId`s generally change.
+    pub fn from_graphdef(def: GraphDefinition<Key, Data, EdgeMeta, Weight>) -> Self {
+        let (node_records, index) = def.nodes.into_parts();
+        let mut nodes = NodeInterner::new();
+        nodes.records = node_records;
+        nodes.index = index;
+
+        let mut edges = def.edges;
+        edges.sort_by_key(|er| (er.from, er.to));
+
+        let mut row_offsets = vec![0; nodes.len() + 1];
+        let mut col_offsets = vec![0; nodes.len() + 1];
+        for er in &edges {
+            row_offsets[er.from.0 + 1] += 1;
+            col_offsets[er.to.0 + 1] += 1;
+        }
+        for v in 0..nodes.len() {
+            row_offsets[v + 1] += row_offsets[v];
+            col_offsets[v + 1] += col_offsets[v];
+        }
+
+        // Edges come sorted by source, so every column is filled in source order
+        let mut row_indices = vec![NodeId(0); edges.len()];
+        let mut next = col_offsets.clone();
+        for er in &edges {
+            row_indices[next[er.to.0]] = er.from;
+            next[er.to.0] += 1;
+        }
+
+        let mut col_indices = Vec::with_capacity(edges.len());
+        let mut meta = Vec::with_capacity(edges.len());
+        let mut weights = Vec::with_capacity(edges.len());
+        for er in edges {
+            col_indices.push(er.to);
+            meta.push(er.meta);
+            weights.push(er.weight);
+        }
+
+        Self {
+            nodes,
+            row_offsets,
+            col_indices,
+            col_offsets,
+            row_indices,
+            meta,
+            weights,
+        }
+    }
+
+    /// Positions of the out-edges of `v`, empty for unknown nodes.
+    fn row(&self, v: NodeId) -> Range<usize> {
+        if v.0 >= self.nodes.len() {
+            return 0..0;
+        }
+        self.row_offsets[v.0]..self.row_offsets[v.0 + 1]
+    }
+
+    /// Source of the edge at position `e`, found by binary search over the row offsets.
+    fn source(&self, e: EdgeId) -> NodeId {
+        NodeId(self.row_offsets.partition_point(|&offset| offset <= e.0) - 1)
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> From<GraphDefinition<Key, Data, EdgeMeta, Weight>>
+    for CsrStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn from(def: GraphDefinition<Key, Data, EdgeMeta, Weight>) -> Self {
+        Self::from_graphdef(def)
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> GraphBase for CsrStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    type Key = Key;
+    type Data = Data;
+    type EdgeMeta = EdgeMeta;
+    type Weight = Weight;
+
+    fn order(&self) -> usize {
+        self.nodes.len()
+    }
+    fn size(&self) -> usize {
+        self.col_indices.len()
+    }
+
+    fn node_id(&self, key: &Self::Key) -> Option<NodeId> {
+        self.nodes.get_id(key)
+    }
+    fn node_ids(&self) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        Box::new((0..self.nodes.len()).map(NodeId))
+    }
+
+    fn node_key(&self, id: NodeId) -> &Self::Key {
+        &self.nodes.get(id).key
+    }
+    fn node_data(&self, id: NodeId) -> &Self::Data {
+        &self.nodes.get(id).data
+    }
+
+    fn edge_ids(&self) -> Box<dyn Iterator<Item = EdgeId> + '_> {
+        Box::new((0..self.col_indices.len()).map(EdgeId))
+    }
+    fn endpoints(&self, e: EdgeId) -> (NodeId, NodeId) {
+        (self.source(e), self.col_indices[e.0])
+    }
+    fn edge_meta(&self, e: EdgeId) -> &Self::EdgeMeta {
+        &self.meta[e.0]
+    }
+    fn edges_between(&self, from: NodeId, to: NodeId) -> Box<dyn Iterator<Item = EdgeId> + '_> {
+        let row = self.row(from);
+        let targets = &self.col_indices[row.clone()];
+        let start = row.start + targets.partition_point(|&t| t < to);
+        let end = row.start + targets.partition_point(|&t| t <= to);
+        Box::new((start..end).map(EdgeId))
+    }
+
+    fn neighborhood(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        let mut neighbors: Vec<NodeId> = self.successors(v).filter(|&u| u != v).collect();
+        neighbors.extend(self.predecessors(v));
+        Box::new(neighbors.into_iter())
+    }
+
+    fn predecessors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        if v.0 >= self.nodes.len() {
+            return Box::new(std::iter::empty());
+        }
+        let column = self.col_offsets[v.0]..self.col_offsets[v.0 + 1];
+        Box::new(self.row_indices[column].iter().copied())
+    }
+    fn successors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        Box::new(self.col_indices[self.row(v)].iter().copied())
+    }
+
+    fn out_degree(&self, v: NodeId) -> usize {
+        self.row(v).len()
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> EdgeWeights for CsrStorage<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    type W = Weight;
+    fn weight_of(&self, e: EdgeId) -> Option<Self::W> {
+        self.weights[e.0]
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight, Target> StorageConvert<Target>
+    for CsrStorage<Key, Data, EdgeMeta, Weight>
+where
+    Target: From<GraphDefinition<Key, Data, EdgeMeta, Weight>>,
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn convert(&self) -> Target {
+        let mut def = GraphDefinition::new();
+        for rec in self.nodes.records.iter() {
+            def.nodes.intern(rec.key.clone(), rec.data.clone());
+        }
+        for e in self.edge_ids() {
+            let (from, to) = self.endpoints(e);
+            def.edges.push(EdgeRecord::new(
+                from,
+                to,
+                self.meta[e.0].clone(),
+                self.weights[e.0],
+            ));
+        }
+        Target::from(def)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::AdjacencyListIn;
+    use std::mem::size_of;
+
+    fn sparse_graph(n: usize, m: usize) -> GraphDefinition<usize> {
+        let mut def = GraphDefinition::new();
+        for v in 0..n {
+            def.add_node(v, ());
+        }
+        for i in 0..m {
+            def.add_edge_by_id(NodeId(i % n), NodeId((i * 7919 + 13) % n), (), None);
+        }
+        def
+    }
+
+    #[test]
+    fn column_index_is_cheaper_than_adjacency_lists() {
+        let def = sparse_graph(10_000, 100_000);
+        let csr = CsrStorage::from(def.clone());
+        let lists = AdjacencyListIn::from(def);
+
+        for v in [0, 1, 4_321, 9_999].map(NodeId) {
+            let mut expected: Vec<NodeId> = lists.predecessors(v).collect();
+            expected.sort();
+            assert_eq!(csr.predecessors(v).collect::<Vec<_>>(), expected);
+        }
+
+        let word = size_of::<usize>();
+        let csr_bytes = word
+            * (csr.row_offsets.len()
+                + csr.col_offsets.len()
+                + csr.col_indices.len()
+                + csr.row_indices.len());
+        let list_bytes = lists.edges.capacity() * size_of::<EdgeRecord<(), ()>>()
+            + [&lists.out_adj, &lists.in_adj]
+                .iter()
+                .flat_map(|adj| adj.iter())
+                .map(|ids| size_of::<Vec<EdgeId>>() + ids.capacity() * word)
+                .sum::<usize>();
+        assert!(
+            csr_bytes < list_bytes,
+            "CSR with column index uses {csr_bytes} bytes, adjacency lists {list_bytes}"
+        );
+    }
+}
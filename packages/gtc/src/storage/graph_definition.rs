@@ -3,8 +3,11 @@
 
 use crate::core::{EdgeId, NodeId};
 use crate::interner::NodeInterner;
-use crate::traits::{GraphBase, MutableStorage, StorageRepresentation};
+use crate::traits::{
+    GraphBase, MutableNodeData, MutableStorage, MutableWeights, StorageRepresentation,
+};
 use crate::{EdgeWeights, StorageConvert};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -92,6 +95,70 @@ where
     }
 }
 
+impl<Key, Data, EdgeMeta, Weight> GraphDefinition<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    /// Weights of the edges between every ordered pair of endpoint keys.
+    fn weights_by_endpoint_keys(&self) -> HashMap<(&Key, &Key), Vec<Option<Weight>>> {
+        let mut groups: HashMap<(&Key, &Key), Vec<Option<Weight>>> = HashMap::new();
+        for edge in &self.edges {
+            let from = &self.nodes.get(edge.from).key;
+            let to = &self.nodes.get(edge.to).key;
+            groups.entry((from, to)).or_default().push(edge.weight);
+        }
+        groups
+    }
+}
+
+/// Identity by keys: two definitions are equal if they have the same node keys and the same
+/// multiset of `(from key, to key, weight)` edges, whatever the insertion order and the
+/// resulting `NodeId`s. Node data and edge metadata are ignored. This is not an isomorphism
+/// test, so graphs that only differ by a relabeling of their nodes compare unequal.
+impl<Key, Data, EdgeMeta, Weight> PartialEq for GraphDefinition<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.order() != other.order() || self.size() != other.size() {
+            return false;
+        }
+        if (0..other.nodes.len())
+            .any(|i| self.nodes.get_id(&other.nodes.get(NodeId(i)).key).is_none())
+        {
+            return false;
+        }
+
+        let ours = self.weights_by_endpoint_keys();
+        let mut theirs = other.weights_by_endpoint_keys();
+        if ours.len() != theirs.len() {
+            return false;
+        }
+
+        // Weights are only `PartialOrd`, so each group is matched pairwise.
+        ours.into_iter().all(|(endpoints, weights)| {
+            let Some(remaining) = theirs.get_mut(&endpoints) else {
+                return false;
+            };
+            weights
+                .into_iter()
+                .all(|w| match remaining.iter().position(|&r| r == w) {
+                    Some(i) => {
+                        remaining.swap_remove(i);
+                        true
+                    }
+                    None => false,
+                })
+        })
+    }
+}
+
 impl<Key, Data, EdgeMeta, Weight> StorageRepresentation
     for GraphDefinition<Key, Data, EdgeMeta, Weight>
 where
@@ -147,6 +214,34 @@ where
     }
 }
 
+impl<Key, Data, EdgeMeta, Weight> MutableWeights for GraphDefinition<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn set_weight(&mut self, e: EdgeId, weight: Option<Self::Weight>) {
+        self.edges[e.0].weight = weight;
+    }
+
+    fn edge_meta_mut(&mut self, e: EdgeId) -> &mut Self::EdgeMeta {
+        &mut self.edges[e.0].meta
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableNodeData for GraphDefinition<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn add_node_or_update(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        self.nodes.intern_or_update(key, data)
+    }
+}
+
 impl<K, D, E, W> GraphBase for GraphDefinition<K, D, E, W>
 where
     K: Eq + std::hash::Hash + Clone + std::fmt::Debug,
@@ -273,3 +368,42 @@ where
         target
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Definition over `&str` keys with the given weighted arcs, nodes added as they appear.
+    fn definition(
+        edges: &[(&'static str, &'static str, i32)],
+    ) -> GraphDefinition<&'static str, (), (), i32> {
+        let mut def = GraphDefinition::new();
+        for &(from, to, weight) in edges {
+            def.add_edge_by_key(from, to, (), (), (), Some(weight));
+        }
+        def
+    }
+
+    #[test]
+    fn equality_ignores_insertion_order() {
+        let first = definition(&[("a", "b", 1), ("b", "c", 2), ("c", "a", 3)]);
+        let second = definition(&[("c", "a", 3), ("b", "c", 2), ("a", "b", 1)]);
+        assert_ne!(first.node_id(&"a"), second.node_id(&"a"));
+        assert!(first == second);
+
+        // Direction matters: `c -> a` is not `a -> c`
+        assert!(first != definition(&[("a", "b", 1), ("b", "c", 2), ("a", "c", 3)]));
+    }
+
+    #[test]
+    fn equality_compares_weight_and_edge_multisets() {
+        let parallel = definition(&[("a", "b", 1), ("a", "b", 2)]);
+        assert!(parallel == definition(&[("a", "b", 2), ("a", "b", 1)]));
+        assert!(parallel != definition(&[("a", "b", 1), ("a", "b", 1)]));
+
+        // Same pairs and size, but the parallel edge sits on a different pair
+        let doubled_ab = definition(&[("a", "b", 1), ("a", "b", 1), ("b", "c", 1)]);
+        let doubled_bc = definition(&[("a", "b", 1), ("b", "c", 1), ("b", "c", 1)]);
+        assert!(doubled_ab != doubled_bc);
+    }
+}
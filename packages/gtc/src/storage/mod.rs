@@ -1,9 +1,17 @@
 pub mod adjacency_list;
 pub mod adjacency_list_in;
 pub mod adjacency_matrix;
+pub mod auto;
+pub mod csr;
 pub mod graph_definition;
+pub mod undirected_adjacency_list;
+pub mod weight_matrix;
 
 pub use adjacency_list::AdjacencyList;
 pub use adjacency_list_in::AdjacencyListIn;
 pub use adjacency_matrix::AdjacencyMatrix;
+pub use auto::{AutoStorage, build_graph};
+pub use csr::CsrStorage;
 pub use graph_definition::GraphDefinition;
+pub use undirected_adjacency_list::UndirectedAdjacencyList;
+pub use weight_matrix::WeightMatrix;
@@ -0,0 +1,343 @@
+//! UndirectedAdjacencyList: stores every undirected edge once and lists it under both endpoints,
+//! so successors, predecessors and neighborhood all answer symmetrically and `size()` is the
+//! true edge count. Intended as the storage of `UndirectedGraph`.
+
+use crate::core::{EdgeId, NodeId};
+use crate::interner::NodeInterner;
+use crate::storage::graph_definition::{EdgeRecord, GraphDefinition};
+use crate::traits::{
+    EdgeWeights, GraphBase, MutableNodeData, MutableStorage, MutableWeights, StorageConvert,
+    StorageRepresentation,
+};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+#[derive(Clone)]
+pub struct UndirectedAdjacencyList<Key = String, Data = (), EdgeMeta = (), Weight = ()>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    pub nodes: NodeInterner<Key, Data>,
+    pub edges: Vec<EdgeRecord<EdgeMeta, Weight>>,
+    /// Edges incident to every node. A self-loop is listed twice under its node.
+    pub incident: Vec<Vec<EdgeId>>,
+}
+
+impl<Key, Data, EdgeMeta, Weight> StorageRepresentation
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    const SYMMETRIC: bool = true;
+
+    fn with_node_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: NodeInterner::new(),
+            edges: Vec::new(),
+            incident: Vec::with_capacity(capacity),
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn push_edge(&mut self, record: EdgeRecord<EdgeMeta, Weight>) -> EdgeId {
+        let needed = record.from.0.max(record.to.0) + 1;
+        if self.incident.len() < needed {
+            self.incident.resize(needed, Vec::new());
+        }
+        let eid = EdgeId(self.edges.len());
+        self.incident[record.from.0].push(eid);
+        self.incident[record.to.0].push(eid);
+        self.edges.push(record);
+        eid
+    }
+
+    /// The endpoint of `e` opposite to `v` (`v` itself for a self-loop).
+    fn other_endpoint(&self, e: EdgeId, v: NodeId) -> NodeId {
+        let r = &self.edges[e.0];
+        if r.from == v { r.to } else { r.from }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    pub fn new() -> Self {
+        Self {
+            nodes: NodeInterner::new(),
+            edges: Vec::new(),
+            incident: Vec::new(),
+        }
+    }
+
+    /// Builds the storage from a definition, pairing every arc with an opposite twin as
+    /// `UndirectedGraph` stores edges in directed storages. Each pair becomes one edge, and
+    /// arcs without a twin are kept as edges on their own.
+    pub fn from_graphdef(def: GraphDefinition<Key, Data, EdgeMeta, Weight>) -> Self {
+        let (node_records, index) = def.nodes.into_parts();
+        let mut nodes = NodeInterner::new();
+        nodes.records = node_records;
+        nodes.index = index;
+
+        let n = nodes.len();
+        let mut al = Self {
+            nodes,
+            edges: Vec::new(),
+            incident: vec![Vec::new(); n],
+        };
+
+        let mut unmatched: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+        for er in def.edges.into_iter() {
+            match unmatched.get_mut(&(er.to, er.from)) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => {
+                    *unmatched.entry((er.from, er.to)).or_default() += 1;
+                    al.push_edge(er);
+                }
+            }
+        }
+        al
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> Default for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> From<GraphDefinition<Key, Data, EdgeMeta, Weight>>
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn from(def: GraphDefinition<Key, Data, EdgeMeta, Weight>) -> Self {
+        Self::from_graphdef(def)
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> GraphBase for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    type Key = Key;
+    type Data = Data;
+    type EdgeMeta = EdgeMeta;
+    type Weight = Weight;
+
+    fn order(&self) -> usize {
+        self.nodes.len()
+    }
+    fn size(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn node_id(&self, key: &Self::Key) -> Option<NodeId> {
+        self.nodes.get_id(key)
+    }
+    fn node_ids(&self) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        Box::new((0..self.nodes.len()).map(NodeId))
+    }
+
+    fn node_key(&self, id: NodeId) -> &Self::Key {
+        &self.nodes.get(id).key
+    }
+    fn node_data(&self, id: NodeId) -> &Self::Data {
+        &self.nodes.get(id).data
+    }
+
+    fn edge_ids(&self) -> Box<dyn Iterator<Item = EdgeId> + '_> {
+        Box::new((0..self.edges.len()).map(EdgeId))
+    }
+    fn endpoints(&self, e: EdgeId) -> (NodeId, NodeId) {
+        let r = &self.edges[e.0];
+        (r.from, r.to)
+    }
+    fn edge_meta(&self, e: EdgeId) -> &Self::EdgeMeta {
+        &self.edges[e.0].meta
+    }
+    /// Edges joining `from` and `to` in either orientation.
+    fn edges_between(&self, from: NodeId, to: NodeId) -> Box<dyn Iterator<Item = EdgeId> + '_> {
+        let Some(incident) = self.incident.get(from.0) else {
+            return Box::new(std::iter::empty());
+        };
+
+        let mut edge_ids: Vec<EdgeId> = incident
+            .iter()
+            .copied()
+            .filter(|&e| self.other_endpoint(e, from) == to)
+            .collect();
+        // A self-loop is listed twice under its node.
+        edge_ids.dedup();
+        Box::new(edge_ids.into_iter())
+    }
+
+    fn neighborhood(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        let Some(incident) = self.incident.get(v.0) else {
+            return Box::new(std::iter::empty());
+        };
+        Box::new(incident.iter().map(move |&e| self.other_endpoint(e, v)))
+    }
+
+    fn successors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        self.neighborhood(v)
+    }
+    fn predecessors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
+        self.neighborhood(v)
+    }
+
+    /// Number of edge endpoints at `v` (a self-loop counts twice).
+    fn degree(&self, v: NodeId) -> usize {
+        self.incident.get(v.0).map_or(0, Vec::len)
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> EdgeWeights
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    type W = Weight;
+    fn weight_of(&self, e: EdgeId) -> Option<Self::W> {
+        self.edges[e.0].weight
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableStorage
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn add_node(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        let id = self.nodes.intern(key, data);
+        if self.incident.len() <= id.0 {
+            self.incident.resize(id.0 + 1, Vec::new());
+        }
+        id
+    }
+
+    fn add_edge_by_id(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        meta: Self::EdgeMeta,
+        weight: Option<Self::Weight>,
+    ) -> EdgeId {
+        self.push_edge(EdgeRecord::new(from, to, meta, weight))
+    }
+
+    fn add_edge_by_key(
+        &mut self,
+        from_key: Self::Key,
+        to_key: Self::Key,
+        from_data: Self::Data,
+        to_data: Self::Data,
+        meta: Self::EdgeMeta,
+        weight: Option<Self::Weight>,
+    ) -> EdgeId {
+        let from = self.add_node(from_key, from_data);
+        let to = self.add_node(to_key, to_data);
+        self.add_edge_by_id(from, to, meta, weight)
+    }
+
+    fn clear_edges(&mut self) {
+        self.edges.clear();
+        for incident in self.incident.iter_mut() {
+            incident.clear();
+        }
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableWeights
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn set_weight(&mut self, e: EdgeId, weight: Option<Self::Weight>) {
+        self.edges[e.0].weight = weight;
+    }
+
+    fn edge_meta_mut(&mut self, e: EdgeId) -> &mut Self::EdgeMeta {
+        &mut self.edges[e.0].meta
+    }
+}
+
+impl<Key, Data, EdgeMeta, Weight> MutableNodeData
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Key: Debug + Clone + Eq + Hash + Default,
+    Data: Debug + Clone + Default,
+    EdgeMeta: Debug + Clone + Default,
+    Weight: Debug + Copy + PartialOrd + Default,
+{
+    fn add_node_or_update(&mut self, key: Self::Key, data: Self::Data) -> NodeId {
+        let id = self.nodes.intern_or_update(key, data);
+        if self.incident.len() <= id.0 {
+            self.incident.resize(id.0 + 1, Vec::new());
+        }
+        id
+    }
+}
+
+/// Converts through a definition holding every edge as two opposite arcs, the layout
+/// `UndirectedGraph` uses in directed storages, so conversions in both directions keep
+/// working with `into_storage` and `into_directed`.
+impl<Key, Data, EdgeMeta, Weight, Target> StorageConvert<Target>
+    for UndirectedAdjacencyList<Key, Data, EdgeMeta, Weight>
+where
+    Target: From<GraphDefinition<Key, Data, EdgeMeta, Weight>>,
+    Key: Debug + Clone + Eq + Hash,
+    Data: Debug + Clone,
+    EdgeMeta: Debug + Clone,
+    Weight: Debug + Copy + PartialOrd,
+{
+    fn convert(&self) -> Target {
+        let mut def = GraphDefinition::new();
+        for rec in self.nodes.records.iter() {
+            def.nodes.intern(rec.key.clone(), rec.data.clone());
+        }
+        for er in self.edges.iter() {
+            def.add_edge_by_id(er.from, er.to, er.meta.clone(), er.weight);
+            def.add_edge_by_id(er.to, er.from, er.meta.clone(), er.weight);
+        }
+        Target::from(def)
+    }
+}
@@ -0,0 +1,142 @@
+//! WeightMatrix: dense matrix holding edge weights directly, for matrix algorithms that need
+//! O(1) weight access by `(i, j)`. It is a read-only companion to AdjacencyMatrix, built from
+//! any weighted graph, and not a storage representation.
+
+use crate::Weight;
+use crate::core::NodeId;
+use crate::traits::{EdgeWeights, StorageRepresentation};
+use crate::wrappers::Graph;
+use std::hash::Hash;
+
+#[derive(Clone, Debug)]
+pub struct WeightMatrix<K, W> {
+    pub n: usize,
+    /// Node keys in node id order, labelling rows and columns.
+    pub nodes: Vec<K>,
+    /// Row-major weights, `None` where there is no edge.
+    pub data: Vec<Option<W>>,
+}
+
+impl<K, W> WeightMatrix<K, W>
+where
+    W: Copy + PartialOrd,
+{
+    /// Indexes rows and columns by node id. Among parallel edges the lightest weight is kept,
+    /// and edges without a weight are left out.
+    pub fn from_graph<G, S>(graph: &G) -> Self
+    where
+        G: Graph<Storage = S> + EdgeWeights<W = W>,
+        S: StorageRepresentation<Key = K>,
+        K: Clone + Eq + Hash,
+    {
+        let n = graph.order();
+        let mut matrix = Self {
+            n,
+            nodes: graph
+                .node_ids()
+                .map(|id| graph.node_key(id).clone())
+                .collect(),
+            data: vec![None; n * n],
+        };
+
+        // Undirected edges fill both cells, also when the storage holds them only once
+        for (e, from, to) in graph.arcs() {
+            let Some(w) = graph.weight_of(e) else {
+                continue;
+            };
+            let cell = &mut matrix.data[from.0 * n + to.0];
+            if cell.is_none_or(|current| w < current) {
+                *cell = Some(w);
+            }
+        }
+
+        matrix
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<W> {
+        self.data[i * self.n + j]
+    }
+
+    pub fn get_by_id(&self, from: NodeId, to: NodeId) -> Option<W> {
+        self.get(from.0, to.0)
+    }
+
+    pub fn row(&self, i: usize) -> &[Option<W>] {
+        &self.data[i * self.n..(i + 1) * self.n]
+    }
+}
+
+impl<K, W> WeightMatrix<K, W>
+where
+    K: Clone,
+    W: Weight,
+{
+    /// Sets every diagonal cell to zero unless a negative self-loop is lighter, so that
+    /// repeated `min_plus_multiply` also keeps the shorter paths found so far.
+    pub fn with_zero_diagonal(mut self) -> Self {
+        for i in 0..self.n {
+            let cell = &mut self.data[i * self.n + i];
+            if cell.is_none_or(|current| W::zero() < current) {
+                *cell = Some(W::zero());
+            }
+        }
+        self
+    }
+
+    /// Tropical (min, +) product: cell `(i, j)` is the lightest `self(i, k) + other(k, j)`
+    /// over all `k`. Squaring the matrix of a graph with a zero diagonal doubles the number
+    /// of edges its lightest paths may use.
+    ///
+    /// # Panics
+    /// Panics if the matrices have different sizes.
+    pub fn min_plus_multiply(&self, other: &Self) -> Self {
+        assert_eq!(self.n, other.n, "Matrices must have the same size");
+
+        let n = self.n;
+        let mut data = vec![None; n * n];
+        for i in 0..n {
+            for k in 0..n {
+                let Some(a) = self.get(i, k) else {
+                    continue;
+                };
+                for j in 0..n {
+                    let Some(b) = other.get(k, j) else {
+                        continue;
+                    };
+                    let cell: &mut Option<W> = &mut data[i * n + j];
+                    if cell.is_none_or(|current| a + b < current) {
+                        *cell = Some(a + b);
+                    }
+                }
+            }
+        }
+
+        Self {
+            n,
+            nodes: self.nodes.clone(),
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Simple, UndirectedAdjacencyList, UndirectedGraph};
+
+    #[test]
+    fn undirected_edges_fill_both_cells() {
+        let graph = UndirectedGraph::<
+            UndirectedAdjacencyList<String, (), (), i32>,
+            Simple,
+            String,
+            (),
+            (),
+            i32,
+        >::from_edges([("a", "b", 3), ("b", "c", 4)]);
+        let matrix = WeightMatrix::from_graph(&graph);
+        assert_eq!(matrix.row(0), &[None, Some(3), None]);
+        assert_eq!(matrix.row(1), &[Some(3), None, Some(4)]);
+        assert_eq!(matrix.row(2), &[None, Some(4), None]);
+    }
+}
@@ -30,12 +30,87 @@ pub trait GraphBase {
 
     fn successors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_>;
     fn predecessors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_>;
+
+    /// Keys and data of all nodes, in node id order.
+    fn nodes_with_data(&self) -> Box<dyn Iterator<Item = (&Self::Key, &Self::Data)> + '_> {
+        Box::new(
+            self.node_ids()
+                .map(move |v| (self.node_key(v), self.node_data(v))),
+        )
+    }
+
+    /// Number of edges leaving `v`.
+    fn out_degree(&self, v: NodeId) -> usize {
+        self.successors(v).count()
+    }
+
+    /// Number of edges entering `v`.
+    fn in_degree(&self, v: NodeId) -> usize {
+        self.predecessors(v).count()
+    }
+
+    /// Number of edge endpoints at `v` (a self-loop counts twice).
+    fn degree(&self, v: NodeId) -> usize {
+        self.in_degree(v) + self.out_degree(v)
+    }
+
+    /// Degrees of all nodes, sorted in descending order.
+    fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees: Vec<usize> = self.node_ids().map(|v| self.degree(v)).collect();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+
+    /// Ratio of stored edges to the number of possible ordered pairs of distinct nodes.
+    /// Undirected graphs store every edge in both directions, so the same formula applies.
+    fn density(&self) -> f64 {
+        let n = self.order();
+        if n < 2 {
+            return 0.0;
+        }
+        self.size() as f64 / (n * (n - 1)) as f64
+    }
+
+    /// Every stored edge, ordered by `(from_key, to_key)` and then by id, so the order does
+    /// not depend on how the graph was built. Undirected graphs over directed storages yield
+    /// both arcs of an edge; `Graph::logical_edges_sorted` lists each edge once.
+    fn edges_sorted(&self) -> Vec<EdgeId>
+    where
+        Self::Key: Ord,
+    {
+        let mut edges: Vec<EdgeId> = self.edge_ids().collect();
+        edges.sort_by(|&a, &b| {
+            let (a_from, a_to) = self.endpoints(a);
+            let (b_from, b_to) = self.endpoints(b);
+            (self.node_key(a_from), self.node_key(a_to), a).cmp(&(
+                self.node_key(b_from),
+                self.node_key(b_to),
+                b,
+            ))
+        });
+        edges
+    }
 }
 
 /// Edge weight lookup
 pub trait EdgeWeights {
     type W: Debug + Copy + PartialOrd;
     fn weight_of(&self, e: EdgeId) -> Option<Self::W>;
+
+    /// Endpoint keys and weight of every stored edge, in edge id order. Undirected wrappers
+    /// store each edge as two opposite arcs, so both are yielded.
+    #[allow(clippy::type_complexity)]
+    fn edges_with_keys(
+        &self,
+    ) -> Box<dyn Iterator<Item = (&Self::Key, &Self::Key, Option<Self::W>)> + '_>
+    where
+        Self: GraphBase,
+    {
+        Box::new(self.edge_ids().map(move |e| {
+            let (from, to) = self.endpoints(e);
+            (self.node_key(from), self.node_key(to), self.weight_of(e))
+        }))
+    }
 }
 
 /// Marker trait: storage types implement this to mark they are a storage representation.
@@ -45,6 +120,10 @@ pub trait StorageRepresentation: GraphBase
 where
     <Self as GraphBase>::Key: Eq + Hash,
 {
+    /// Whether every edge is stored once and reported from both endpoints, as undirected
+    /// storages do. `UndirectedGraph` otherwise stores each edge as two opposite arcs.
+    const SYMMETRIC: bool = false;
+
     fn with_node_capacity(capacity: usize) -> Self;
 }
 
@@ -74,6 +153,30 @@ where
     fn clear_edges(&mut self);
 }
 
+/// Storages that can replace the data of an existing node. Kept apart from `MutableStorage`
+/// so storages outside this crate do not have to implement it.
+pub trait MutableNodeData: MutableStorage
+where
+    <Self as GraphBase>::Key: Eq + Hash,
+{
+    /// Like `add_node`, but replaces the data of a node whose key is already present.
+    fn add_node_or_update(&mut self, key: Self::Key, data: Self::Data) -> NodeId;
+}
+
+/// Storages whose edges can be changed in place after they were added.
+pub trait MutableWeights: MutableStorage
+where
+    <Self as GraphBase>::Key: Eq + Hash,
+{
+    /// Replaces the weight of a stored edge. Undirected wrappers over directed storages keep
+    /// each edge as two arcs; use their `set_edge_weight` to update both.
+    fn set_weight(&mut self, e: EdgeId, weight: Option<Self::Weight>);
+    /// Mutable access to the metadata of a stored edge. Undirected wrappers over directed
+    /// storages keep each edge as two arcs whose metadata is independent, so the caller has
+    /// to update both to keep them in sync.
+    fn edge_meta_mut(&mut self, e: EdgeId) -> &mut Self::EdgeMeta;
+}
+
 /// Trait for converting between storage representations (expensive, may allocate).
 /// Implementations should convert `Self` into `Target` storage type.
 pub trait StorageConvert<Target> {
@@ -83,7 +186,12 @@ pub trait StorageConvert<Target> {
 /// Graph kind marker traits (type-level markers)
 /// These are empty marker traits implemented by zero-sized types you can pass to wrappers.
 /// Use these marker types as the GK generic parameter on wrappers.
-pub trait GraphKindMarker {}
+pub trait GraphKindMarker {
+    /// Whether graphs of this kind may contain self-loops.
+    const ALLOWS_SELF_LOOPS: bool;
+    /// Whether graphs of this kind may contain parallel edges.
+    const ALLOWS_PARALLEL_EDGES: bool;
+}
 pub trait SimpleGraphKind: GraphKindMarker {}
 pub trait PseudoGraphKind: GraphKindMarker {}
 pub trait MultiGraphKind: GraphKindMarker {}
@@ -103,16 +211,104 @@ impl IsUnit for () {}
 // NotUnit implemented for any type that implements the Weight trait (numeric-like)
 impl<T> NotUnit for T where T: Weight {}
 
-/// Marker for nodes that have total ordering (placeholder)
-pub trait OrderedNodes {}
+/// Capability bound for algorithms that need a canonical vertex order, such as Prüfer
+/// sequences picking the smallest leaf. Every totally ordered key type has it.
+pub trait OrderedNodes: Ord {}
+
+impl<T: Ord> OrderedNodes for T {}
 
-/// Merge strategies (placeholder)
-#[derive(Clone, Debug)]
+/// How `union` combines the nodes of two graphs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum MergeStrategy {
-    /// Relabel nodes (default)
+    /// Keep the graphs disjoint; nodes of the second graph whose key is taken get a fresh key
+    #[default]
     Relabel,
-    /// Merge by key equality
+    /// Identify nodes with equal keys
     MergeByKey,
-    /// Merge by globally provided UID (requires UID in node data)
+    /// Identify nodes with equal `NodeUid::uid`; nodes without a uid are never merged
     MergeByUid,
 }
+
+/// Key types that can make up a new key when `MergeStrategy::Relabel` meets a clash.
+pub trait FreshKey: Sized {
+    /// A key derived from `self` for which `taken` is false.
+    fn fresh(&self, taken: impl Fn(&Self) -> bool) -> Self;
+}
+
+/// Appends primes: `a`, `a'`, `a''`, ...
+impl FreshKey for String {
+    fn fresh(&self, taken: impl Fn(&Self) -> bool) -> Self {
+        let mut key = self.clone();
+        while taken(&key) {
+            key.push('\'');
+        }
+        key
+    }
+}
+
+/// Counts up from the original key.
+impl FreshKey for usize {
+    fn fresh(&self, taken: impl Fn(&Self) -> bool) -> Self {
+        let mut key = *self;
+        while taken(&key) {
+            key += 1;
+        }
+        key
+    }
+}
+
+/// Node data carrying a globally unique id, for `MergeStrategy::MergeByUid`. Data without
+/// one can use the default, which never merges.
+pub trait NodeUid {
+    fn uid(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl NodeUid for () {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DirectedGraph, GraphDefinition, Simple, UndirectedAdjacencyList, UndirectedGraph};
+
+    fn complete_pairs(n: usize) -> Vec<(String, String)> {
+        (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i.to_string(), j.to_string())))
+            .collect()
+    }
+
+    fn assert_complete<G: GraphBase>(k5: &G) {
+        assert_eq!(k5.degree_sequence(), vec![4; 5]);
+        assert_eq!(k5.density(), 1.0);
+    }
+
+    #[test]
+    fn complete_graph_degrees_and_density() {
+        assert_complete(
+            &UndirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges(
+                complete_pairs(5),
+            ),
+        );
+        assert_complete(&UndirectedGraph::<
+            UndirectedAdjacencyList<String>,
+            Simple,
+            String,
+        >::from_edges(complete_pairs(5)));
+    }
+
+    #[test]
+    fn directed_path_degrees_and_density() {
+        let path = DirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+        ]);
+        let b = path.node_id(&"b".to_string()).unwrap();
+        assert_eq!(
+            (path.in_degree(b), path.out_degree(b), path.degree(b)),
+            (1, 1, 2)
+        );
+        assert_eq!(path.degree_sequence(), vec![2, 1, 1]);
+        assert_eq!(path.density(), 2.0 / 6.0);
+    }
+}
@@ -0,0 +1,82 @@
+//! Small data structures shared by several algorithms.
+
+use std::cmp::Ordering;
+
+/// Disjoint Set Union (DSU) / Union-Find over the elements `0..n`, with path compression
+/// and union by rank.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            count: n,
+        }
+    }
+
+    /// Adds a new singleton set and returns its element.
+    pub fn push(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(i);
+        self.rank.push(0);
+        self.count += 1;
+        i
+    }
+
+    /// Representative of the set containing `i`.
+    pub fn find(&mut self, i: usize) -> usize {
+        if self.parent[i] != i {
+            // Path compression: point directly to root
+            self.parent[i] = self.find(self.parent[i]);
+        }
+        self.parent[i]
+    }
+
+    /// Merges the sets of `i` and `j`. Returns false if they were already the same set.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let root_i = self.find(i);
+        let root_j = self.find(j);
+
+        if root_i != root_j {
+            // Union by rank: attach smaller tree to larger tree
+            match self.rank[root_i].cmp(&self.rank[root_j]) {
+                Ordering::Less => self.parent[root_i] = root_j,
+                Ordering::Greater => self.parent[root_j] = root_i,
+                Ordering::Equal => {
+                    self.parent[root_j] = root_i;
+                    self.rank[root_i] += 1;
+                }
+            }
+            self.count -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn connected(&mut self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// SplitMix64 step, mapped to a float in `[0, 1)`. Small and dependency-free; layout
+/// seeding and the random generators only need reproducibility, not statistical quality.
+pub(crate) fn next_unit(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
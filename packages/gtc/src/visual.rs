@@ -1,9 +1,62 @@
-use std::{collections::HashMap, f64::consts::PI};
+use std::{
+    collections::{BTreeMap, HashMap},
+    f64::consts::PI,
+};
+
+use crate::util::next_unit;
+
+/// Graphs with more nodes than this use grid-approximated repulsion in the layout.
+/// Smaller graphs keep the exact all-pairs computation.
+const GRID_REPULSION_MIN_NODES: usize = 100;
 
 pub struct VisualGraphData {
     pub labels: Vec<String>,
     pub edges: Vec<VisualEdge>,
     pub is_directed: bool,
+    /// Extra TikZ options per node (e.g. `fill=red!30`), appended to the `main node` style.
+    /// Missing or `None` entries keep the default style.
+    pub node_styles: Vec<Option<String>>,
+    /// Fixed `(x, y)` coordinates for every node. When set, the force-directed
+    /// layout is skipped and node `i` is placed at `positions[i]`.
+    pub positions: Option<Vec<(f64, f64)>>,
+    /// Seed for the initial layout positions. Without one nodes start on a circle.
+    /// Either way the layout does not depend on the order of `edges`.
+    pub seed: Option<u64>,
+    /// Edges `(u, v)` drawn in a highlight style, e.g. a shortest path or a spanning tree.
+    /// In undirected graphs `(v, u)` matches as well.
+    pub highlight_edges: Vec<(usize, usize)>,
+}
+
+impl VisualGraphData {
+    pub fn new(labels: Vec<String>, edges: Vec<VisualEdge>, is_directed: bool) -> Self {
+        Self {
+            labels,
+            edges,
+            is_directed,
+            node_styles: Vec::new(),
+            positions: None,
+            seed: None,
+            highlight_edges: Vec::new(),
+        }
+    }
+
+    /// Pins every node to the given coordinates instead of running the layout.
+    pub fn with_positions(mut self, positions: Vec<(f64, f64)>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    /// Appends TikZ options to the style of each node, e.g. `fill=red!30` for a color class.
+    pub fn with_node_styles(mut self, node_styles: Vec<Option<String>>) -> Self {
+        self.node_styles = node_styles;
+        self
+    }
+
+    /// Starts the layout from seeded pseudo-random positions.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
 }
 
 pub struct VisualEdge {
@@ -12,27 +65,42 @@ pub struct VisualEdge {
     pub label: Option<String>,
 }
 
-// --- Core Function ---
+/// Escapes text for use in LaTeX text mode. Characters are mapped one at a time so the
+/// braces introduced by one escape are not escaped again. `^` and `~` become text commands,
+/// since `\^` and `\~` are accents that would swallow the next character.
+pub(crate) fn escape_latex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '%' | '&' | '#' | '_' | '{' | '}' | '$' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
 
-pub fn generate_latex_graph(data: VisualGraphData) -> String {
+/// Node positions in TikZ units, one per label: the pinned `positions` if set, otherwise
+/// the result of the force-directed layout. Deterministic for the same data.
+///
+/// Graphs with more than `GRID_REPULSION_MIN_NODES` nodes only repel nearby nodes, so their
+/// coordinates differ from what the exact all-pairs layout would give.
+pub fn compute_layout(data: &VisualGraphData) -> Vec<(f64, f64)> {
+    layout(data, GRID_REPULSION_MIN_NODES)
+}
+
+/// `compute_layout` with exact repulsion up to `grid_min_nodes` nodes.
+fn layout(data: &VisualGraphData, grid_min_nodes: usize) -> Vec<(f64, f64)> {
     let n = data.labels.len();
     if n == 0 {
-        return "\\begin{figure}[htbp]\\begin{tikzpicture}\n% empty graph\n\\end{tikzpicture}\\end{figure}".to_string();
+        return Vec::new();
     }
 
-    // --- Helper: Latex Escaping ---
-    let escape_latex = |s: &str| -> String {
-        s.replace('\\', "\\textbackslash{}")
-            .replace('%', "\\%")
-            .replace('&', "\\&")
-            .replace('#', "\\#")
-            .replace('_', "\\_")
-            .replace('{', "\\{")
-            .replace('}', "\\}")
-            .replace('$', "\\$")
-            .replace('^', "\\^")
-    };
-
     // --- Physics Simulation (Force-Directed Layout) ---
     #[derive(Clone, Copy)]
     struct Point {
@@ -40,17 +108,32 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
         y: f64,
     }
 
-    // 1. Initialize positions in a circle
+    // 1. Initialize positions: pinned ones if given, otherwise seeded or in a circle
     let radius = (n as f64).sqrt() * 2.0;
-    let mut pos: Vec<Point> = (0..n)
-        .map(|i| {
-            let angle = 2.0 * PI * (i as f64) / (n as f64);
-            Point {
-                x: radius * angle.cos(),
-                y: radius * angle.sin(),
-            }
-        })
-        .collect();
+    let mut pos: Vec<Point> = match (&data.positions, data.seed) {
+        (Some(positions), _) => {
+            assert_eq!(positions.len(), n, "Every node needs a position");
+            positions.iter().map(|&(x, y)| Point { x, y }).collect()
+        }
+        (None, Some(seed)) => {
+            let mut state = seed;
+            (0..n)
+                .map(|_| Point {
+                    x: radius * (2.0 * next_unit(&mut state) - 1.0),
+                    y: radius * (2.0 * next_unit(&mut state) - 1.0),
+                })
+                .collect()
+        }
+        (None, None) => (0..n)
+            .map(|i| {
+                let angle = 2.0 * PI * (i as f64) / (n as f64);
+                Point {
+                    x: radius * angle.cos(),
+                    y: radius * angle.sin(),
+                }
+            })
+            .collect(),
+    };
 
     // 2. Build adjacency for physics (treat everything as undirected attraction)
     let mut adj: Vec<Vec<usize>> = vec![vec![]; n];
@@ -60,11 +143,16 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
             adj[edge.v].push(edge.u);
         }
     }
+    // Sum forces in node order so the result does not depend on edge order
+    for neighbors in &mut adj {
+        neighbors.sort_unstable();
+    }
 
     // 3. Physics Constants
     let width = (n as f64).sqrt() * 5.0;
     let k_opt = (width * width / (n as f64)).sqrt();
-    let iterations = 100;
+    // Pinned positions skip the simulation entirely
+    let iterations = if data.positions.is_some() { 0 } else { 100 };
     let mut temp = width / 10.0;
 
     // 4. Simulation Loop
@@ -72,15 +160,45 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
         let mut disp = vec![Point { x: 0.0, y: 0.0 }; n];
 
         // Repulsive forces
-        for v in 0..n {
-            for u in 0..n {
-                if u != v {
-                    let dx = pos[v].x - pos[u].x;
-                    let dy = pos[v].y - pos[u].y;
-                    let dist = (dx * dx + dy * dy).sqrt().max(0.01);
-                    let force = (k_opt * k_opt) / dist;
-                    disp[v].x += (dx / dist) * force;
-                    disp[v].y += (dy / dist) * force;
+        let repel = |v: usize, u: usize, disp: &mut [Point]| {
+            let dx = pos[v].x - pos[u].x;
+            let dy = pos[v].y - pos[u].y;
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let force = (k_opt * k_opt) / dist;
+            disp[v].x += (dx / dist) * force;
+            disp[v].y += (dy / dist) * force;
+        };
+
+        if n <= grid_min_nodes {
+            for v in 0..n {
+                for u in 0..n {
+                    if u != v {
+                        repel(v, u, &mut disp);
+                    }
+                }
+            }
+        } else {
+            // Grid variant of Fruchterman–Reingold: bucket nodes into cells of size 2k and
+            // only repel against the 3x3 block of cells around each node, since the force
+            // from distant nodes is small. This brings a round close to O(n).
+            let cell = 2.0 * k_opt;
+            let cell_of = |p: Point| ((p.x / cell).floor() as i64, (p.y / cell).floor() as i64);
+
+            let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+            for (v, &p) in pos.iter().enumerate() {
+                grid.entry(cell_of(p)).or_default().push(v);
+            }
+
+            for (v, &p) in pos.iter().enumerate() {
+                let (cx, cy) = cell_of(p);
+                for gx in cx - 1..=cx + 1 {
+                    for gy in cy - 1..=cy + 1 {
+                        for &u in grid.get(&(gx, gy)).into_iter().flatten() {
+                            if u != v {
+                                repel(v, u, &mut disp);
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -109,24 +227,52 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
         temp *= 0.95;
     }
 
+    pos.into_iter().map(|p| (p.x, p.y)).collect()
+}
+
+// --- Core Function ---
+
+pub fn generate_latex_graph(data: VisualGraphData) -> String {
+    let n = data.labels.len();
+    if n == 0 {
+        return "\\begin{figure}[htbp]\\begin{tikzpicture}\n% empty graph\n\\end{tikzpicture}\\end{figure}".to_string();
+    }
+    let pos = compute_layout(&data);
+
     // --- Generate Nodes ---
     let mut nodes_tex = String::new();
     for i in 0..n {
         let label = escape_latex(&data.labels[i]);
+        let style = match data.node_styles.get(i) {
+            Some(Some(style)) => format!("main node, {}", style),
+            _ => "main node".to_string(),
+        };
         nodes_tex.push_str(&format!(
-            "  \\node[main node] (n{}) at ({:.3},{:.3}) {{{}}};\n",
-            i, pos[i].x, pos[i].y, label
+            "  \\node[{}] (n{}) at ({:.3},{:.3}) {{{}}};\n",
+            style, i, pos[i].0, pos[i].1, label
         ));
     }
 
     // --- Generate Edges ---
     let mut edges_tex = String::new();
     let base_style = "draw opacity=1, line width=0.8pt";
+    let highlight_style = "draw=red, draw opacity=1, line width=1.6pt";
     let arrow_style = if data.is_directed { "->" } else { "-" };
+    let edge_style = |from: usize, to: usize| {
+        let highlighted = data
+            .highlight_edges
+            .iter()
+            .any(|&(a, b)| (a, b) == (from, to) || (!data.is_directed && (b, a) == (from, to)));
+        if highlighted {
+            highlight_style
+        } else {
+            base_style
+        }
+    };
 
-    // Grouping logic
-    let mut pair_groups: HashMap<(usize, usize), (Vec<&VisualEdge>, Vec<&VisualEdge>)> =
-        HashMap::new();
+    // Grouping logic, ordered by node pair so the output is stable
+    let mut pair_groups: BTreeMap<(usize, usize), (Vec<&VisualEdge>, Vec<&VisualEdge>)> =
+        BTreeMap::new();
 
     for edge in &data.edges {
         let u = edge.u;
@@ -164,7 +310,13 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
 
                 edges_tex.push_str(&format!(
                     "  \\draw[{}, {}, looseness=10] (n{}) to[out={}, in={}] {} (n{});\n",
-                    arrow_style, base_style, u, out_angle, in_angle, w_lbl, v
+                    arrow_style,
+                    edge_style(u, v),
+                    u,
+                    out_angle,
+                    in_angle,
+                    w_lbl,
+                    v
                 ));
             }
             continue;
@@ -215,7 +367,12 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
 
                     edges_tex.push_str(&format!(
                         "  \\draw[{}, {}, {}] (n{}) to {} (n{});\n",
-                        arrow_style, base_style, bend_str, from, w_lbl, to
+                        arrow_style,
+                        edge_style(from, to),
+                        bend_str,
+                        from,
+                        w_lbl,
+                        to
                     ));
                 }
             };
@@ -242,3 +399,242 @@ pub fn generate_latex_graph(data: VisualGraphData) -> String {
         nodes_tex, edges_tex
     )
 }
+
+/// Escapes text for use in SVG/XML content and attribute values.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders the graph as a standalone SVG document, using the same layout as
+/// `generate_latex_graph`. Parallel edges are drawn on top of each other, and
+/// `node_styles` (TikZ options) are ignored.
+pub fn generate_svg_graph(data: VisualGraphData) -> String {
+    const SCALE: f64 = 40.0;
+    const MARGIN: f64 = 40.0;
+    const NODE_RADIUS: f64 = 14.0;
+
+    let pos = compute_layout(&data);
+
+    // Layout units to pixels; SVG's y axis points down, TikZ's points up
+    let min_x = pos.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let max_y = pos.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    let points: Vec<(f64, f64)> = pos
+        .iter()
+        .map(|&(x, y)| (MARGIN + (x - min_x) * SCALE, MARGIN + (max_y - y) * SCALE))
+        .collect();
+    let width = points.iter().map(|p| p.0).fold(0.0, f64::max) + MARGIN;
+    let height = points.iter().map(|p| p.1).fold(0.0, f64::max) + MARGIN;
+
+    let is_highlighted = |from: usize, to: usize| {
+        data.highlight_edges
+            .iter()
+            .any(|&(a, b)| (a, b) == (from, to) || (!data.is_directed && (b, a) == (from, to)))
+    };
+
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.3} {:.3}\">\n",
+        width.ceil(),
+        height.ceil(),
+        width,
+        height
+    ));
+    if data.is_directed {
+        svg.push_str(
+            "  <defs>\n    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\">\n      <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"context-stroke\"/>\n    </marker>\n  </defs>\n",
+        );
+    }
+
+    // --- Edges ---
+    svg.push_str("  <g font-family=\"sans-serif\" font-size=\"11\" text-anchor=\"middle\">\n");
+    let marker = if data.is_directed {
+        " marker-end=\"url(#arrow)\""
+    } else {
+        ""
+    };
+    for edge in &data.edges {
+        let (u, v) = (edge.u, edge.v);
+        let (stroke, stroke_width) = if is_highlighted(u, v) {
+            ("red", 2.4)
+        } else {
+            ("black", 1.2)
+        };
+        let (x1, y1) = points[u];
+        let (x2, y2) = points[v];
+
+        let label_at = if u == v {
+            // Self-loop: a teardrop above the node
+            let r = NODE_RADIUS;
+            svg.push_str(&format!(
+                "    <path d=\"M {:.3} {:.3} C {:.3} {:.3} {:.3} {:.3} {:.3} {:.3}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"{}/>\n",
+                x1 - r * 0.6,
+                y1 - r * 0.8,
+                x1 - r * 2.0,
+                y1 - r * 3.5,
+                x1 + r * 2.0,
+                y1 - r * 3.5,
+                x1 + r * 0.6,
+                y1 - r * 0.8,
+                stroke,
+                stroke_width,
+                marker
+            ));
+            (x1, y1 - r * 3.0)
+        } else {
+            // Shorten the line so it ends at the node's border, where the arrowhead belongs
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let (ox, oy) = (dx / dist * NODE_RADIUS, dy / dist * NODE_RADIUS);
+            svg.push_str(&format!(
+                "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\" stroke=\"{}\" stroke-width=\"{}\"{}/>\n",
+                x1 + ox,
+                y1 + oy,
+                x2 - ox,
+                y2 - oy,
+                stroke,
+                stroke_width,
+                marker
+            ));
+            ((x1 + x2) / 2.0, (y1 + y2) / 2.0 - 4.0)
+        };
+
+        if let Some(label) = &edge.label {
+            svg.push_str(&format!(
+                "    <text x=\"{:.3}\" y=\"{:.3}\">{}</text>\n",
+                label_at.0,
+                label_at.1,
+                escape_xml(label)
+            ));
+        }
+    }
+    svg.push_str("  </g>\n");
+
+    // --- Nodes ---
+    svg.push_str(
+        "  <g font-family=\"sans-serif\" font-size=\"12\" font-weight=\"bold\" text-anchor=\"middle\" dominant-baseline=\"central\">\n",
+    );
+    for (i, &(x, y)) in points.iter().enumerate() {
+        svg.push_str(&format!(
+            "    <circle cx=\"{:.3}\" cy=\"{:.3}\" r=\"{}\" fill=\"white\" stroke=\"black\" stroke-width=\"1.2\"/>\n",
+            x, y, NODE_RADIUS
+        ));
+        svg.push_str(&format!(
+            "    <text x=\"{:.3}\" y=\"{:.3}\">{}</text>\n",
+            x,
+            y,
+            escape_xml(&data.labels[i])
+        ));
+    }
+    svg.push_str("  </g>\n</svg>\n");
+
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            escape_latex("a^b~c_d&e#f%g$h{i}"),
+            "a\\textasciicircum{}b\\textasciitilde{}c\\_d\\&e\\#f\\%g\\$h\\{i\\}"
+        );
+        assert_eq!(escape_latex("\\"), "\\textbackslash{}");
+        assert_eq!(escape_latex("plain v1"), "plain v1");
+    }
+
+    #[test]
+    fn node_styles_extend_the_main_node_style() {
+        let data = VisualGraphData::new(
+            vec!["a".to_string(), "b".to_string()],
+            vec![VisualEdge {
+                u: 0,
+                v: 1,
+                label: None,
+            }],
+            false,
+        )
+        .with_positions(vec![(0.0, 0.0), (1.0, 0.0)])
+        .with_node_styles(vec![Some("fill=red!30".to_string())]);
+
+        let tex = generate_latex_graph(data);
+        assert!(tex.contains("\\node[main node, fill=red!30] (n0)"));
+        assert!(tex.contains("\\node[main node] (n1)"));
+    }
+
+    #[test]
+    fn output_does_not_depend_on_edge_order() {
+        // Every pair of distinct nodes among 8, listed in two different orders
+        let pairs: Vec<(usize, usize)> = (0..8)
+            .flat_map(|u| (u + 1..8).map(move |v| (u, v)))
+            .collect();
+        let mut shuffled: Vec<usize> = (0..pairs.len()).collect();
+        shuffled.sort_by_key(|&i| (i * 11) % pairs.len());
+
+        let data = |order: &[usize], seed: Option<u64>| {
+            let labels = (0..8).map(|v| v.to_string()).collect();
+            let edges = order
+                .iter()
+                .map(|&i| VisualEdge {
+                    u: pairs[i].0,
+                    v: pairs[i].1,
+                    label: Some(i.to_string()),
+                })
+                .collect();
+            let data = VisualGraphData::new(labels, edges, false);
+            match seed {
+                Some(seed) => data.with_seed(seed),
+                None => data,
+            }
+        };
+
+        let in_order: Vec<usize> = (0..pairs.len()).collect();
+        for seed in [None, Some(7)] {
+            assert_eq!(
+                compute_layout(&data(&in_order, seed)),
+                compute_layout(&data(&shuffled, seed))
+            );
+            assert_eq!(
+                generate_latex_graph(data(&in_order, seed)),
+                generate_latex_graph(data(&shuffled, seed))
+            );
+        }
+    }
+
+    /// Times the exact and the grid layout on 500 nodes. Run with
+    /// `cargo test --release -p gtc -- --ignored --nocapture grid_layout_benchmark`.
+    #[test]
+    #[ignore]
+    fn grid_layout_benchmark() {
+        use std::time::Instant;
+
+        let n = 500;
+        let edges = (0..n)
+            .flat_map(|v| [(v, (v + 1) % n), (v, (v * 7 + 3) % n)])
+            .map(|(u, v)| VisualEdge { u, v, label: None })
+            .collect();
+        let data = VisualGraphData::new((0..n).map(|v| v.to_string()).collect(), edges, false);
+
+        let start = Instant::now();
+        layout(&data, usize::MAX);
+        let exact = start.elapsed();
+        let start = Instant::now();
+        compute_layout(&data);
+        let grid = start.elapsed();
+
+        println!("{n} nodes: exact {exact:?}, grid {grid:?}");
+        assert!(grid < exact);
+    }
+}
@@ -3,9 +3,13 @@
 //! They also carry marker types (Simple / Pseudo / Multi) as type-level graph kind parameters
 //! that select different behaviors at compile time.
 
+use crate::Weight;
 use crate::core::{EdgeId, NodeId};
+use crate::latex::WeightLabel;
+use crate::storage::{AdjacencyList, AdjacencyMatrix, GraphDefinition};
 use crate::traits::*;
-use std::fmt::Debug;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
@@ -23,6 +27,141 @@ where
 
     fn storage(&self) -> &Self::Storage;
     fn storage_mut(&mut self) -> &mut Self::Storage;
+
+    fn is_directed(&self) -> bool;
+
+    /// One edge id per logical edge. Undirected graphs over directed storages store every
+    /// edge as two opposite arcs, so each arc is paired with an unmatched twin and only the
+    /// first is kept.
+    fn logical_edge_ids(&self) -> Vec<EdgeId> {
+        if self.is_directed() || <Self::Storage as StorageRepresentation>::SYMMETRIC {
+            return self.edge_ids().collect();
+        }
+
+        let mut unmatched: HashMap<(NodeId, NodeId), Vec<EdgeId>> = HashMap::new();
+        let mut edges = Vec::new();
+        for e in self.edge_ids() {
+            let (from, to) = self.endpoints(e);
+            let twin = unmatched.get_mut(&(to, from)).and_then(|twins| twins.pop());
+            if twin.is_none() {
+                unmatched.entry((from, to)).or_default().push(e);
+                edges.push(e);
+            }
+        }
+        edges
+    }
+
+    /// Every edge as `(id, from, to)` arcs that can be followed in that direction. Undirected
+    /// graphs over symmetric storages hold each edge once, so it is also listed reversed
+    /// (self-loops only once); other graphs already store one arc per direction.
+    fn arcs(&self) -> Vec<(EdgeId, NodeId, NodeId)> {
+        let twins = !self.is_directed() && <Self::Storage as StorageRepresentation>::SYMMETRIC;
+
+        let mut arcs = Vec::new();
+        for e in self.edge_ids() {
+            let (from, to) = self.endpoints(e);
+            arcs.push((e, from, to));
+            if twins && from != to {
+                arcs.push((e, to, from));
+            }
+        }
+        arcs
+    }
+
+    /// Sum of the weights of the edges at `v` (its strength), counting a self-loop twice
+    /// like `degree` does. `None` if any of these edges has no weight.
+    fn weighted_degree(&self, v: NodeId) -> Option<<Self as EdgeWeights>::W>
+    where
+        Self: EdgeWeights,
+        <Self as EdgeWeights>::W: Weight,
+    {
+        // Undirected graphs over directed storages list every edge as an arc out of both
+        // endpoints, so the out-arcs alone cover each incident edge once.
+        let both_ends = self.is_directed() || <Self::Storage as StorageRepresentation>::SYMMETRIC;
+
+        let mut total = <Self as EdgeWeights>::W::zero();
+        for e in self.edge_ids() {
+            let (from, to) = self.endpoints(e);
+            let ends = usize::from(from == v) + usize::from(both_ends && to == v);
+            for _ in 0..ends {
+                total = total + self.weight_of(e)?;
+            }
+        }
+        Some(total)
+    }
+
+    fn has_self_loop(&self) -> bool {
+        self.edge_ids().any(|e| {
+            let (from, to) = self.endpoints(e);
+            from == to
+        })
+    }
+
+    /// Logical edges whose endpoints coincide.
+    fn self_loops(&self) -> Vec<EdgeId> {
+        self.logical_edge_ids()
+            .into_iter()
+            .filter(|&e| {
+                let (from, to) = self.endpoints(e);
+                from == to
+            })
+            .collect()
+    }
+
+    fn has_parallel_edges(&self) -> bool {
+        !self.parallel_edge_groups().is_empty()
+    }
+
+    /// Logical edges grouped by endpoints, keeping only groups of two or more. Undirected
+    /// edges are grouped regardless of endpoint order. Groups are ordered by their first edge.
+    fn parallel_edge_groups(&self) -> Vec<Vec<EdgeId>> {
+        let mut group_of: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+        let mut groups: Vec<Vec<EdgeId>> = Vec::new();
+        for e in self.logical_edge_ids() {
+            let (from, to) = self.endpoints(e);
+            let pair = if self.is_directed() || from <= to {
+                (from, to)
+            } else {
+                (to, from)
+            };
+            let index = *group_of.entry(pair).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(e);
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+
+    /// One `(id, from, to)` per logical edge, ordered by `(from_key, to_key)` and then by id.
+    /// Undirected edges are turned so that `from_key <= to_key`, so both the order and the
+    /// orientation are the same however the graph was built.
+    fn logical_edges_sorted(&self) -> Vec<(EdgeId, NodeId, NodeId)>
+    where
+        Self::Key: Ord,
+    {
+        let mut edges: Vec<(EdgeId, NodeId, NodeId)> = self
+            .logical_edge_ids()
+            .into_iter()
+            .map(|e| {
+                let (from, to) = self.endpoints(e);
+                if !self.is_directed() && self.node_key(to) < self.node_key(from) {
+                    (e, to, from)
+                } else {
+                    (e, from, to)
+                }
+            })
+            .collect();
+        edges.sort_by(|&(a, a_from, a_to), &(b, b_from, b_to)| {
+            (self.node_key(a_from), self.node_key(a_to), a).cmp(&(
+                self.node_key(b_from),
+                self.node_key(b_to),
+                b,
+            ))
+        });
+        edges
+    }
 }
 
 // Zero-sized marker types for graph kinds
@@ -33,13 +172,56 @@ pub struct Pseudo;
 #[derive(Clone, Copy, Debug)]
 pub struct Multi;
 
-impl GraphKindMarker for Simple {}
+impl GraphKindMarker for Simple {
+    const ALLOWS_SELF_LOOPS: bool = false;
+    const ALLOWS_PARALLEL_EDGES: bool = false;
+}
 impl SimpleGraphKind for Simple {}
-impl GraphKindMarker for Pseudo {}
+impl GraphKindMarker for Pseudo {
+    const ALLOWS_SELF_LOOPS: bool = true;
+    const ALLOWS_PARALLEL_EDGES: bool = true;
+}
 impl PseudoGraphKind for Pseudo {}
-impl GraphKindMarker for Multi {}
+impl GraphKindMarker for Multi {
+    const ALLOWS_SELF_LOOPS: bool = false;
+    const ALLOWS_PARALLEL_EDGES: bool = true;
+}
 impl MultiGraphKind for Multi {}
 
+/// What `Simple` graphs do when asked to add an edge between already adjacent nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgePolicy {
+    /// Reject the duplicate with an error (default).
+    #[default]
+    Error,
+    /// Silently skip the duplicate, keeping the first edge and returning its id.
+    KeepFirst,
+}
+
+/// Which of several parallel edges survives when they are collapsed into one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WeightMerge {
+    /// The lightest edge (default). Weighted edges win over unweighted ones.
+    #[default]
+    Min,
+    /// The heaviest edge. Weighted edges win over unweighted ones.
+    Max,
+    /// The first edge in edge order.
+    First,
+}
+
+impl WeightMerge {
+    /// Whether `candidate` should replace the currently kept weight `kept`.
+    fn prefers<W: PartialOrd>(self, candidate: Option<W>, kept: Option<W>) -> bool {
+        match (self, candidate, kept) {
+            (WeightMerge::First, _, _) | (_, None, _) => false,
+            (_, Some(_), None) => true,
+            (WeightMerge::Min, Some(c), Some(k)) => c < k,
+            (WeightMerge::Max, Some(c), Some(k)) => c > k,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct DirectedGraph<S, GK = Simple, K = String, D = (), E = (), W = ()>
 where
@@ -53,6 +235,8 @@ where
     _d: PhantomData<D>,
     _e: PhantomData<E>,
     _w: PhantomData<W>,
+    edge_policy: EdgePolicy,
+    max_degree: Option<usize>,
 }
 
 impl<S, GK, Key, Data, EdgeMeta, Weight> DirectedGraph<S, GK, Key, Data, EdgeMeta, Weight>
@@ -80,9 +264,37 @@ where
             _d: PhantomData,
             _e: PhantomData,
             _w: PhantomData,
+            edge_policy: EdgePolicy::default(),
+            max_degree: None,
         }
     }
 
+    /// Carries the edge policy and max degree of the graph this one was derived from.
+    fn with_settings(mut self, edge_policy: EdgePolicy, max_degree: Option<usize>) -> Self {
+        self.edge_policy = edge_policy;
+        self.max_degree = max_degree;
+        self
+    }
+
+    /// Sets how `Simple` graphs handle duplicate edges; other kinds accept parallel edges.
+    pub fn set_edge_policy(&mut self, policy: EdgePolicy) {
+        self.edge_policy = policy;
+    }
+
+    pub fn edge_policy(&self) -> EdgePolicy {
+        self.edge_policy
+    }
+
+    /// Caps the degree of every node: checked adds that would push an endpoint over `cap`
+    /// fail. Edges already in the graph are not checked. `None` (the default) disables it.
+    pub fn set_max_degree(&mut self, cap: Option<usize>) {
+        self.max_degree = cap;
+    }
+
+    pub fn max_degree(&self) -> Option<usize> {
+        self.max_degree
+    }
+
     /// Convert storage representation to another storage type.
     pub fn convert_storage<TargetS>(&self) -> TargetS
     where
@@ -101,6 +313,27 @@ where
         let new = self.storage.convert();
         DirectedGraph::new(new)
     }
+
+    /// Copy of the graph over another storage, e.g.
+    /// `let m: DirectedGraph<AdjacencyMatrix> = g.map_storage();`. The edge policy is kept.
+    pub fn map_storage<TargetS>(&self) -> DirectedGraph<TargetS, GK, Key, Data, EdgeMeta, Weight>
+    where
+        S: StorageConvert<TargetS>,
+        TargetS:
+            StorageRepresentation<Key = Key, Data = Data, EdgeMeta = EdgeMeta, Weight = Weight>,
+    {
+        DirectedGraph::new(self.storage.convert()).with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Compares the storages, which for `GraphDefinition` means the same node keys and the
+    /// same weighted edges by key, regardless of insertion order. This is identity by keys,
+    /// not an isomorphism test.
+    pub fn structural_eq(&self, other: &Self) -> bool
+    where
+        S: PartialEq,
+    {
+        self.storage == other.storage
+    }
 }
 
 /// Implement Graph trait for DirectedGraph
@@ -120,6 +353,9 @@ where
     fn storage_mut(&mut self) -> &mut Self::Storage {
         &mut self.storage
     }
+    fn is_directed(&self) -> bool {
+        true
+    }
 }
 
 /// Implement GraphBase by delegating to storage
@@ -201,6 +437,152 @@ where
     }
 }
 
+impl<S, GK, K, D, E, W> DirectedGraph<S, GK, K, D, E, W>
+where
+    S: MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + EdgeWeights<W = W>
+        + GraphBase<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    /// Contracts arc `e` into a fresh graph, leaving `self` untouched.
+    ///
+    /// Both endpoints merge into one node that keeps the key and data of the endpoint
+    /// with the lower `NodeId`; the data of the other endpoint is discarded. Arcs incident
+    /// to either endpoint are redirected to the merged node. The contracted arc is removed,
+    /// and resulting self-loops or parallel arcs are dropped when the kind forbids them.
+    pub fn contract_edge(&self, e: EdgeId) -> Self {
+        let edges: Vec<EdgeId> = self.storage.edge_ids().filter(|&f| f != e).collect();
+        Self::new(contract_storage::<S, GK>(
+            &self.storage,
+            self.storage.endpoints(e),
+            &edges,
+            false,
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// The underlying simple graph as a fresh graph: self-loops are dropped and every group of
+    /// parallel arcs is collapsed into the one picked by `merge`, with its metadata.
+    pub fn to_simple(&self, merge: WeightMerge) -> DirectedGraph<S, Simple, K, D, E, W> {
+        let edges: Vec<EdgeId> = self.storage.edge_ids().collect();
+        DirectedGraph::new(simple_storage(&self.storage, &edges, false, merge))
+            .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Symmetrizes the graph: every arc `a -> b` becomes an undirected edge `a -- b`. For kinds
+    /// without parallel edges, opposite arcs collapse into one edge that keeps the lighter
+    /// weight and its metadata (the first arc on ties); other kinds keep an edge per arc.
+    pub fn into_undirected<TargetS>(self) -> UndirectedGraph<TargetS, GK, K, D, E, W>
+    where
+        TargetS: MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
+            + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    {
+        let mut target = TargetS::with_node_capacity(self.storage.order());
+        for v in self.storage.node_ids() {
+            target.add_node(
+                self.storage.node_key(v).clone(),
+                self.storage.node_data(v).clone(),
+            );
+        }
+
+        let edges: Vec<EdgeId> = if GK::ALLOWS_PARALLEL_EDGES {
+            self.storage.edge_ids().collect()
+        } else {
+            merge_parallel(
+                &self.storage,
+                self.storage.edge_ids(),
+                true,
+                WeightMerge::Min,
+            )
+        };
+        for e in edges {
+            let (from, to) = self.storage.endpoints(e);
+            add_undirected_edge(
+                &mut target,
+                from,
+                to,
+                self.storage.edge_meta(e).clone(),
+                self.storage.weight_of(e),
+            );
+        }
+
+        UndirectedGraph::new(target).with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// A fresh graph with the nodes and edges of both graphs, where `strategy` decides which
+    /// nodes of `other` are identified with nodes of `self`: `union_relabel`, `union_by_key`
+    /// or `union_by_uid`.
+    pub fn union(&self, other: &Self, strategy: MergeStrategy) -> Self
+    where
+        K: FreshKey,
+        D: NodeUid,
+    {
+        match strategy {
+            MergeStrategy::Relabel => self.union_relabel(other),
+            MergeStrategy::MergeByKey => self.union_by_key(other),
+            MergeStrategy::MergeByUid => self.union_by_uid(other),
+        }
+    }
+
+    /// A fresh graph with the nodes and edges of both graphs, kept disjoint: nodes of `other`
+    /// whose key is taken get a fresh key. Edges that would become forbidden self-loops or
+    /// parallel edges are dropped, as in the other unions. Nodes of `self` come first, in order.
+    pub fn union_relabel(&self, other: &Self) -> Self
+    where
+        K: FreshKey,
+    {
+        Self::new(union_storage::<S, GK>(
+            &self.storage,
+            &other.storage,
+            add_fresh_node,
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Like `union_relabel`, but nodes with equal keys are identified and keep the data of
+    /// the first of them.
+    pub fn union_by_key(&self, other: &Self) -> Self {
+        Self::new(union_storage::<S, GK>(
+            &self.storage,
+            &other.storage,
+            |union, key, data| {
+                union
+                    .node_id(key)
+                    .unwrap_or_else(|| union.add_node(key.clone(), data.clone()))
+            },
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Like `union_relabel`, but nodes with equal `NodeUid::uid` are identified and keep the
+    /// data of the first of them. Nodes without a uid are never merged.
+    pub fn union_by_uid(&self, other: &Self) -> Self
+    where
+        K: FreshKey,
+        D: NodeUid,
+    {
+        let mut by_uid: HashMap<u64, NodeId> = HashMap::new();
+        Self::new(union_storage::<S, GK>(
+            &self.storage,
+            &other.storage,
+            |union, key, data| {
+                let Some(uid) = data.uid() else {
+                    return add_fresh_node(union, key, data);
+                };
+                *by_uid
+                    .entry(uid)
+                    .or_insert_with(|| add_fresh_node(union, key, data))
+            },
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+}
+
 /// === Mutating behavior for DirectedGraph depending on GraphKind ===
 /// We provide different impl blocks conditioned on GK marker trait:
 /// - For Simple (default) => disallow self-loops and parallel edges
@@ -209,18 +591,182 @@ where
 ///
 /// Storage must implement MutableStorage. Wrapper methods return Result to report constraint violations.
 
-/// Generic helper: scan for any existing edge from->to
-fn has_edge_between<S>(storage: &S, from: NodeId, to: NodeId) -> bool
+/// Copies `storage` with the endpoints of `merged` fused into the lower of the two node ids,
+/// keeping only `edges`. Symmetric storages get every kept edge added in both directions.
+/// When parallel edges are not allowed, the first of a merged group is kept.
+fn contract_storage<S, GK>(
+    storage: &S,
+    merged: (NodeId, NodeId),
+    edges: &[EdgeId],
+    symmetric: bool,
+) -> S
 where
-    S: GraphBase,
+    S: MutableStorage + EdgeWeights<W = <S as GraphBase>::Weight>,
+    <S as GraphBase>::Key: Clone + Eq + Hash,
+    <S as GraphBase>::Data: Clone,
+    <S as GraphBase>::EdgeMeta: Clone,
+    GK: GraphKindMarker,
 {
-    for e in storage.edge_ids() {
-        let (f, t) = storage.endpoints(e);
-        if f == from && t == to {
-            return true;
+    let keep = merged.0.min(merged.1);
+    let removed = merged.0.max(merged.1);
+
+    let mut contracted = S::with_node_capacity(storage.order() - usize::from(keep != removed));
+    let mut new_ids = vec![None; storage.order()];
+    for v in storage.node_ids() {
+        if v != removed {
+            new_ids[v.0] = Some(
+                contracted.add_node(storage.node_key(v).clone(), storage.node_data(v).clone()),
+            );
+        }
+    }
+    new_ids[removed.0] = new_ids[keep.0];
+
+    for &e in edges {
+        let (from, to) = storage.endpoints(e);
+        let from = new_ids[from.0].expect("Every node is mapped");
+        let to = new_ids[to.0].expect("Every node is mapped");
+
+        if from == to && !GK::ALLOWS_SELF_LOOPS {
+            continue;
+        }
+        if !GK::ALLOWS_PARALLEL_EDGES && contracted.edges_between(from, to).next().is_some() {
+            continue;
+        }
+
+        let meta = storage.edge_meta(e).clone();
+        let weight = storage.weight_of(e);
+        contracted.add_edge_by_id(from, to, meta.clone(), weight);
+        if symmetric && !S::SYMMETRIC {
+            contracted.add_edge_by_id(to, from, meta, weight);
         }
     }
-    false
+
+    contracted
+}
+
+/// The non-loop `edges` with one edge per group of parallel ones, as chosen by `merge`, in
+/// the order the groups first appear. Undirected graphs group edges regardless of endpoint order.
+fn merge_parallel<S>(
+    storage: &S,
+    edges: impl IntoIterator<Item = EdgeId>,
+    undirected: bool,
+    merge: WeightMerge,
+) -> Vec<EdgeId>
+where
+    S: GraphBase + EdgeWeights<W = <S as GraphBase>::Weight>,
+    <S as GraphBase>::Weight: PartialOrd,
+{
+    let mut pairs: Vec<(NodeId, NodeId)> = Vec::new();
+    let mut kept: HashMap<(NodeId, NodeId), EdgeId> = HashMap::new();
+    for e in edges {
+        let (from, to) = storage.endpoints(e);
+        if from == to {
+            continue;
+        }
+        let pair = if undirected && from > to {
+            (to, from)
+        } else {
+            (from, to)
+        };
+        match kept.get_mut(&pair) {
+            Some(current) => {
+                if merge.prefers(storage.weight_of(e), storage.weight_of(*current)) {
+                    *current = e;
+                }
+            }
+            None => {
+                kept.insert(pair, e);
+                pairs.push(pair);
+            }
+        }
+    }
+
+    pairs.into_iter().map(|pair| kept[&pair]).collect()
+}
+
+/// Copies `storage` without self-loops, keeping one of every group of parallel `edges` as
+/// chosen by `merge`. Undirected graphs group edges regardless of endpoint order and, over
+/// directed storages, get both arcs of every kept edge.
+fn simple_storage<S>(storage: &S, edges: &[EdgeId], undirected: bool, merge: WeightMerge) -> S
+where
+    S: MutableStorage + EdgeWeights<W = <S as GraphBase>::Weight>,
+    <S as GraphBase>::Key: Clone + Eq + Hash,
+    <S as GraphBase>::Data: Clone,
+    <S as GraphBase>::EdgeMeta: Clone,
+    <S as GraphBase>::Weight: PartialOrd,
+{
+    let mut simple = S::with_node_capacity(storage.order());
+    for v in storage.node_ids() {
+        simple.add_node(storage.node_key(v).clone(), storage.node_data(v).clone());
+    }
+
+    // Node ids are preserved, since the nodes were copied in order
+    for e in merge_parallel(storage, edges.iter().copied(), undirected, merge) {
+        let (from, to) = storage.endpoints(e);
+        let meta = storage.edge_meta(e).clone();
+        let weight = storage.weight_of(e);
+        if undirected {
+            add_undirected_edge(&mut simple, from, to, meta, weight);
+        } else {
+            simple.add_edge_by_id(from, to, meta, weight);
+        }
+    }
+
+    simple
+}
+
+/// Copies `first` and then `second` into one storage. `place` puts every node into the
+/// union, either as an existing node it is merged with or as a new one, and returns its id.
+/// Every stored edge is copied, unless merging turned it into a self-loop or a parallel edge
+/// the kind forbids.
+fn union_storage<S, GK>(
+    first: &S,
+    second: &S,
+    mut place: impl FnMut(&mut S, &<S as GraphBase>::Key, &<S as GraphBase>::Data) -> NodeId,
+) -> S
+where
+    S: MutableStorage + EdgeWeights<W = <S as GraphBase>::Weight>,
+    <S as GraphBase>::Key: Clone + Eq + Hash,
+    <S as GraphBase>::Data: Clone,
+    <S as GraphBase>::EdgeMeta: Clone,
+    GK: GraphKindMarker,
+{
+    let mut union = S::with_node_capacity(first.order() + second.order());
+
+    for part in [first, second] {
+        let new_ids: Vec<NodeId> = part
+            .node_ids()
+            .map(|v| place(&mut union, part.node_key(v), part.node_data(v)))
+            .collect();
+
+        for e in part.edge_ids() {
+            let (from, to) = part.endpoints(e);
+            let (from, to) = (new_ids[from.0], new_ids[to.0]);
+            if from == to && !GK::ALLOWS_SELF_LOOPS {
+                continue;
+            }
+            if !GK::ALLOWS_PARALLEL_EDGES && union.edges_between(from, to).next().is_some() {
+                continue;
+            }
+            union.add_edge_by_id(from, to, part.edge_meta(e).clone(), part.weight_of(e));
+        }
+    }
+    union
+}
+
+/// Adds `key` to `union`, freshened first if another node already has it.
+fn add_fresh_node<S>(
+    union: &mut S,
+    key: &<S as GraphBase>::Key,
+    data: &<S as GraphBase>::Data,
+) -> NodeId
+where
+    S: MutableStorage,
+    <S as GraphBase>::Key: Clone + Eq + Hash + FreshKey,
+    <S as GraphBase>::Data: Clone,
+{
+    let key = key.fresh(|k| union.node_id(k).is_some());
+    union.add_node(key, data.clone())
 }
 
 /// Impl for Simple graphs (no self-loops, no parallel edges)
@@ -245,9 +791,15 @@ where
         if from == to {
             return Err("Simple graph: self-loops are not allowed".to_string());
         }
-        if has_edge_between(&self.storage, from, to) {
-            return Err("Simple graph: parallel edges are not allowed".to_string());
+        if let Some(existing) = self.storage.edges_between(from, to).next() {
+            return match self.edge_policy {
+                EdgePolicy::Error => {
+                    Err("Simple graph: parallel edges are not allowed".to_string())
+                }
+                EdgePolicy::KeepFirst => Ok(existing),
+            };
         }
+        check_max_degree(self, self.max_degree, from, to)?;
         Ok(self.storage.add_edge_by_id(from, to, meta, weight))
     }
 
@@ -286,6 +838,42 @@ where
         let to = self.storage.add_node(to_key, to_data);
         self.add_arc_checked(from, to, meta, weight)
     }
+
+    /// Builds a graph with node data and edge metadata. Nodes are added first so their data
+    /// is kept; endpoints that only appear in `edges` get default data. Returns the first
+    /// constraint violation (self-loop or parallel edge) as an error.
+    pub fn try_from_full<NI, EI>(nodes: NI, edges: EI) -> Result<Self, String>
+    where
+        NI: IntoIterator<Item = (K, D)>,
+        EI: IntoIterator<Item = (K, K, E, Option<W>)>,
+    {
+        let mut graph = Self::new(S::with_node_capacity(0));
+        for (key, data) in nodes {
+            graph.storage.add_node(key, data);
+        }
+
+        for (from_key, to_key, meta, weight) in edges {
+            graph.add_arc_by_key_checked(
+                from_key,
+                to_key,
+                D::default(),
+                D::default(),
+                meta,
+                weight,
+            )?;
+        }
+
+        Ok(graph)
+    }
+
+    /// Like `try_from_full`, but panics on a constraint violation.
+    pub fn from_full<NI, EI>(nodes: NI, edges: EI) -> Self
+    where
+        NI: IntoIterator<Item = (K, D)>,
+        EI: IntoIterator<Item = (K, K, E, Option<W>)>,
+    {
+        Self::try_from_full(nodes, edges).unwrap()
+    }
 }
 
 /// Impl for Pseudo graphs (allow self-loops and parallel edges)
@@ -307,6 +895,7 @@ where
         meta: E,
         weight: Option<W>,
     ) -> Result<EdgeId, String> {
+        check_max_degree(self, self.max_degree, from, to)?;
         Ok(self.storage.add_edge_by_id(from, to, meta, weight))
     }
 
@@ -366,6 +955,7 @@ where
         if from == to {
             return Err("Multi graph: self-loops are not allowed".to_string());
         }
+        check_max_degree(self, self.max_degree, from, to)?;
         Ok(self.storage.add_edge_by_id(from, to, meta, weight))
     }
 
@@ -411,7 +1001,12 @@ where
         + StorageRepresentation<Key = K, Data = (), EdgeMeta = (), Weight = ()>,
     K: Debug + Clone + Eq + Hash + Default,
 {
-    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_isolated_nodes_and_edges<UK, NI, EI>(
+        nodes_iter: NI,
+        edges_iter: EI,
+    ) -> Result<Self, String>
     where
         UK: Into<K>,
         NI: IntoIterator<Item = UK>,
@@ -427,15 +1022,24 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key) in edges_iter {
-            graph
-                .add_arc_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))
-                .unwrap();
+            graph.add_arc_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))?;
         }
 
-        graph
+        Ok(graph)
     }
 
-    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        NI: IntoIterator<Item = UK>,
+        EI: IntoIterator<Item = (UK, UK)>,
+    {
+        Self::try_from_isolated_nodes_and_edges(nodes_iter, edges_iter).unwrap()
+    }
+
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_edges<UK, EI>(edges_iter: EI) -> Result<Self, String>
     where
         UK: Into<K>,
         EI: IntoIterator<Item = (UK, UK)>,
@@ -444,12 +1048,18 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key) in edges_iter {
-            graph
-                .add_arc_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))
-                .unwrap();
+            graph.add_arc_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))?;
         }
 
-        graph
+        Ok(graph)
+    }
+
+    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        EI: IntoIterator<Item = (UK, UK)>,
+    {
+        Self::try_from_edges(edges_iter).unwrap()
     }
 }
 
@@ -461,7 +1071,12 @@ where
     K: Debug + Clone + Eq + Hash + Default,
     W: Debug + Copy + PartialOrd + NotUnit,
 {
-    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_isolated_nodes_and_edges<UK, NI, EI>(
+        nodes_iter: NI,
+        edges_iter: EI,
+    ) -> Result<Self, String>
     where
         UK: Into<K>,
         NI: IntoIterator<Item = UK>,
@@ -477,15 +1092,31 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key, weight) in edges_iter {
-            graph
-                .add_arc_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(weight))
-                .unwrap();
+            graph.add_arc_by_key_checked(
+                from_key.into(),
+                to_key.into(),
+                (),
+                (),
+                (),
+                Some(weight),
+            )?;
         }
 
-        graph
+        Ok(graph)
     }
 
-    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        NI: IntoIterator<Item = UK>,
+        EI: IntoIterator<Item = (UK, UK, W)>,
+    {
+        Self::try_from_isolated_nodes_and_edges(nodes_iter, edges_iter).unwrap()
+    }
+
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_edges<UK, EI>(edges_iter: EI) -> Result<Self, String>
     where
         UK: Into<K>,
         EI: IntoIterator<Item = (UK, UK, W)>,
@@ -494,12 +1125,25 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key, weight) in edges_iter {
-            graph
-                .add_arc_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(weight))
-                .unwrap();
+            graph.add_arc_by_key_checked(
+                from_key.into(),
+                to_key.into(),
+                (),
+                (),
+                (),
+                Some(weight),
+            )?;
         }
 
-        graph
+        Ok(graph)
+    }
+
+    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        EI: IntoIterator<Item = (UK, UK, W)>,
+    {
+        Self::try_from_edges(edges_iter).unwrap()
     }
 }
 
@@ -517,6 +1161,8 @@ where
     _d: PhantomData<D>,
     _e: PhantomData<E>,
     _w: PhantomData<W>,
+    edge_policy: EdgePolicy,
+    max_degree: Option<usize>,
 }
 
 impl<S, GK, K, D, E, W> UndirectedGraph<S, GK, K, D, E, W>
@@ -533,9 +1179,37 @@ where
             _d: PhantomData,
             _e: PhantomData,
             _w: PhantomData,
+            edge_policy: EdgePolicy::default(),
+            max_degree: None,
         }
     }
 
+    /// Carries the edge policy and max degree of the graph this one was derived from.
+    fn with_settings(mut self, edge_policy: EdgePolicy, max_degree: Option<usize>) -> Self {
+        self.edge_policy = edge_policy;
+        self.max_degree = max_degree;
+        self
+    }
+
+    /// Sets how `Simple` graphs handle duplicate edges; other kinds accept parallel edges.
+    pub fn set_edge_policy(&mut self, policy: EdgePolicy) {
+        self.edge_policy = policy;
+    }
+
+    pub fn edge_policy(&self) -> EdgePolicy {
+        self.edge_policy
+    }
+
+    /// Caps the degree of every node: checked adds that would push an endpoint over `cap`
+    /// fail. Edges already in the graph are not checked. `None` (the default) disables it.
+    pub fn set_max_degree(&mut self, cap: Option<usize>) {
+        self.max_degree = cap;
+    }
+
+    pub fn max_degree(&self) -> Option<usize> {
+        self.max_degree
+    }
+
     /// Convert storage similarly
     pub fn into_storage<TargetS>(self) -> UndirectedGraph<TargetS, GK, K, D, E, W>
     where
@@ -546,14 +1220,32 @@ where
         UndirectedGraph::new(new)
     }
 
+    /// Copy of the graph over another storage, e.g.
+    /// `let m: UndirectedGraph<AdjacencyMatrix> = g.map_storage();`. The edge policy is kept.
+    pub fn map_storage<TargetS>(&self) -> UndirectedGraph<TargetS, GK, K, D, E, W>
+    where
+        S: StorageConvert<TargetS>,
+        TargetS: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    {
+        UndirectedGraph::new(self.storage.convert())
+            .with_settings(self.edge_policy, self.max_degree)
+    }
+
     /// Convert undirected to directed explicitly (user must request)
     pub fn into_directed<TargetS>(self) -> DirectedGraph<TargetS, GK, K, D, E, W>
     where
         S: StorageConvert<TargetS>,
         TargetS: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
     {
-        let new = self.storage.convert();
-        DirectedGraph::new(new)
+        DirectedGraph::new(self.storage.convert()).with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Same as `DirectedGraph::structural_eq`: identity by keys, not isomorphism.
+    pub fn structural_eq(&self, other: &Self) -> bool
+    where
+        S: PartialEq,
+    {
+        self.storage == other.storage
     }
 }
 
@@ -573,6 +1265,9 @@ where
     fn storage_mut(&mut self) -> &mut Self::Storage {
         &mut self.storage
     }
+    fn is_directed(&self) -> bool {
+        false
+    }
 }
 
 impl<S, GK, K, D, E, W> GraphBase for UndirectedGraph<S, GK, K, D, E, W>
@@ -633,6 +1328,75 @@ where
     fn predecessors(&self, v: NodeId) -> Box<dyn Iterator<Item = NodeId> + '_> {
         self.storage.neighborhood(v)
     }
+
+    /// Directed storages hold every undirected edge as two arcs and symmetric ones list it
+    /// under both endpoints, so either way the stored out-arcs of `v` give the degree.
+    fn degree(&self, v: NodeId) -> usize {
+        self.storage.out_degree(v)
+    }
+    fn in_degree(&self, v: NodeId) -> usize {
+        self.degree(v)
+    }
+    fn out_degree(&self, v: NodeId) -> usize {
+        self.degree(v)
+    }
+
+    /// Symmetric storages hold each edge once, so it counts for both ordered pairs.
+    fn density(&self) -> f64 {
+        let n = self.order();
+        if n < 2 {
+            return 0.0;
+        }
+        let arcs = if S::SYMMETRIC {
+            2 * self.size()
+        } else {
+            self.size()
+        };
+        arcs as f64 / (n * (n - 1)) as f64
+    }
+}
+
+/// Fails if a new edge `a`-`b` would give either endpoint a degree above `cap`. A self-loop
+/// adds two to the degree of its node.
+fn check_max_degree<G>(graph: &G, cap: Option<usize>, a: NodeId, b: NodeId) -> Result<(), String>
+where
+    G: GraphBase,
+{
+    let Some(cap) = cap else {
+        return Ok(());
+    };
+    let endpoints: &[(NodeId, usize)] = if a == b { &[(a, 2)] } else { &[(a, 1), (b, 1)] };
+    for &(v, added) in endpoints {
+        if graph.degree(v) + added > cap {
+            return Err(format!(
+                "Max degree: node {} would exceed degree {}",
+                v.0, cap
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Adds the undirected edge `a`-`b` to `storage`: once if the storage is symmetric, otherwise
+/// as the arcs `a -> b` and `b -> a`. Symmetric storages return the same id twice.
+fn add_undirected_edge<S>(
+    storage: &mut S,
+    a: NodeId,
+    b: NodeId,
+    meta: <S as GraphBase>::EdgeMeta,
+    weight: Option<<S as GraphBase>::Weight>,
+) -> (EdgeId, EdgeId)
+where
+    S: MutableStorage,
+    <S as GraphBase>::Key: Eq + Hash,
+{
+    if S::SYMMETRIC {
+        let e = storage.add_edge_by_id(a, b, meta, weight);
+        return (e, e);
+    }
+    let e1 = storage.add_edge_by_id(a, b, meta.clone(), weight);
+    let e2 = storage.add_edge_by_id(b, a, meta, weight);
+    (e1, e2)
 }
 
 /// Mutating operations for undirected graph add symmetric edges into the underlying storage.
@@ -662,16 +1426,22 @@ where
         if a == b {
             return Err("Simple undirected graph: self-loops not allowed".to_string());
         }
-        // scan for existing a->b or b->a edge
-        for e in self.storage.edge_ids() {
-            let (f, t) = self.storage.endpoints(e);
-            if (f == a && t == b) || (f == b && t == a) {
-                return Err("Simple undirected graph: parallel edges not allowed".to_string());
-            }
+        // look for an existing a->b or b->a edge
+        let forward = self.storage.edges_between(a, b).next();
+        let backward = self.storage.edges_between(b, a).next();
+        if forward.is_some() || backward.is_some() {
+            return match self.edge_policy {
+                EdgePolicy::Error => {
+                    Err("Simple undirected graph: parallel edges not allowed".to_string())
+                }
+                EdgePolicy::KeepFirst => Ok((
+                    forward.or(backward).expect("An edge exists"),
+                    backward.or(forward).expect("An edge exists"),
+                )),
+            };
         }
-        let e1 = self.storage.add_edge_by_id(a, b, meta.clone(), weight);
-        let e2 = self.storage.add_edge_by_id(b, a, meta, weight);
-        Ok((e1, e2))
+        check_max_degree(self, self.max_degree, a, b)?;
+        Ok(add_undirected_edge(&mut self.storage, a, b, meta, weight))
     }
 
     pub fn add_edge(&mut self, a: NodeId, b: NodeId, meta: E) -> Result<(EdgeId, EdgeId), String>
@@ -707,6 +1477,40 @@ where
         let b = self.storage.add_node(b_key, b_data);
         self.add_edge_checked(a, b, meta, weight)
     }
+
+    /// Undirected counterpart of `DirectedGraph::try_from_full`.
+    pub fn try_from_full<NI, EI>(nodes: NI, edges: EI) -> Result<Self, String>
+    where
+        NI: IntoIterator<Item = (K, D)>,
+        EI: IntoIterator<Item = (K, K, E, Option<W>)>,
+    {
+        let mut graph = Self::new(S::with_node_capacity(0));
+        for (key, data) in nodes {
+            graph.storage.add_node(key, data);
+        }
+
+        for (a_key, b_key, meta, weight) in edges {
+            graph.add_edge_by_key_checked(
+                a_key,
+                b_key,
+                D::default(),
+                D::default(),
+                meta,
+                weight,
+            )?;
+        }
+
+        Ok(graph)
+    }
+
+    /// Like `try_from_full`, but panics on a constraint violation.
+    pub fn from_full<NI, EI>(nodes: NI, edges: EI) -> Self
+    where
+        NI: IntoIterator<Item = (K, D)>,
+        EI: IntoIterator<Item = (K, K, E, Option<W>)>,
+    {
+        Self::try_from_full(nodes, edges).unwrap()
+    }
 }
 
 /// Pseudo undirected graph impl (allow self-loops and parallel edges)
@@ -728,9 +1532,8 @@ where
         weight: Option<W>,
     ) -> Result<(EdgeId, EdgeId), String> {
         // allow everything: self-loops and parallel edges permitted
-        let e1 = self.storage.add_edge_by_id(a, b, meta.clone(), weight);
-        let e2 = self.storage.add_edge_by_id(b, a, meta, weight);
-        Ok((e1, e2))
+        check_max_degree(self, self.max_degree, a, b)?;
+        Ok(add_undirected_edge(&mut self.storage, a, b, meta, weight))
     }
 
     pub fn add_edge(&mut self, a: NodeId, b: NodeId, meta: E) -> Result<(EdgeId, EdgeId), String>
@@ -789,9 +1592,8 @@ where
         if a == b {
             return Err("Multi undirected graph: self-loops not allowed".to_string());
         }
-        let e1 = self.storage.add_edge_by_id(a, b, meta.clone(), weight);
-        let e2 = self.storage.add_edge_by_id(b, a, meta, weight);
-        Ok((e1, e2))
+        check_max_degree(self, self.max_degree, a, b)?;
+        Ok(add_undirected_edge(&mut self.storage, a, b, meta, weight))
     }
 
     pub fn add_edge(&mut self, a: NodeId, b: NodeId, meta: E) -> Result<(EdgeId, EdgeId), String>
@@ -836,7 +1638,12 @@ where
         + StorageRepresentation<Key = K, Data = (), EdgeMeta = (), Weight = ()>,
     K: Debug + Clone + Eq + Hash + Default,
 {
-    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_isolated_nodes_and_edges<UK, NI, EI>(
+        nodes_iter: NI,
+        edges_iter: EI,
+    ) -> Result<Self, String>
     where
         UK: Into<K>,
         NI: IntoIterator<Item = UK>,
@@ -852,15 +1659,24 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key) in edges_iter {
-            graph
-                .add_edge_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))
-                .unwrap();
+            graph.add_edge_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))?;
         }
 
-        graph
+        Ok(graph)
     }
 
-    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        NI: IntoIterator<Item = UK>,
+        EI: IntoIterator<Item = (UK, UK)>,
+    {
+        Self::try_from_isolated_nodes_and_edges(nodes_iter, edges_iter).unwrap()
+    }
+
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_edges<UK, EI>(edges_iter: EI) -> Result<Self, String>
     where
         UK: Into<K>,
         EI: IntoIterator<Item = (UK, UK)>,
@@ -869,12 +1685,18 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key) in edges_iter {
-            graph
-                .add_edge_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))
-                .unwrap();
+            graph.add_edge_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(()))?;
         }
 
-        graph
+        Ok(graph)
+    }
+
+    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        EI: IntoIterator<Item = (UK, UK)>,
+    {
+        Self::try_from_edges(edges_iter).unwrap()
     }
 }
 
@@ -886,7 +1708,12 @@ where
     K: Debug + Clone + Eq + Hash + Default,
     W: Debug + Copy + PartialOrd + NotUnit,
 {
-    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_isolated_nodes_and_edges<UK, NI, EI>(
+        nodes_iter: NI,
+        edges_iter: EI,
+    ) -> Result<Self, String>
     where
         UK: Into<K>,
         NI: IntoIterator<Item = UK>,
@@ -902,15 +1729,31 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key, weight) in edges_iter {
-            graph
-                .add_edge_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(weight))
-                .unwrap();
+            graph.add_edge_by_key_checked(
+                from_key.into(),
+                to_key.into(),
+                (),
+                (),
+                (),
+                Some(weight),
+            )?;
         }
 
-        graph
+        Ok(graph)
     }
 
-    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    pub fn from_isolated_nodes_and_edges<UK, NI, EI>(nodes_iter: NI, edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        NI: IntoIterator<Item = UK>,
+        EI: IntoIterator<Item = (UK, UK, W)>,
+    {
+        Self::try_from_isolated_nodes_and_edges(nodes_iter, edges_iter).unwrap()
+    }
+
+    /// Returns the first constraint violation (self-loop or parallel edge) as an error
+    /// instead of panicking.
+    pub fn try_from_edges<UK, EI>(edges_iter: EI) -> Result<Self, String>
     where
         UK: Into<K>,
         EI: IntoIterator<Item = (UK, UK, W)>,
@@ -919,12 +1762,25 @@ where
         let mut graph = Self::new(storage);
 
         for (from_key, to_key, weight) in edges_iter {
-            graph
-                .add_edge_by_key_checked(from_key.into(), to_key.into(), (), (), (), Some(weight))
-                .unwrap();
+            graph.add_edge_by_key_checked(
+                from_key.into(),
+                to_key.into(),
+                (),
+                (),
+                (),
+                Some(weight),
+            )?;
         }
 
-        graph
+        Ok(graph)
+    }
+
+    pub fn from_edges<UK, EI>(edges_iter: EI) -> Self
+    where
+        UK: Into<K>,
+        EI: IntoIterator<Item = (UK, UK, W)>,
+    {
+        Self::try_from_edges(edges_iter).unwrap()
     }
 }
 
@@ -945,17 +1801,561 @@ where
     }
 }
 
-// /// Blanket impl: if A can convert to B, then DirectedGraph<A> -> DirectedGraph<B> via From (implicit)
-// impl<A, B, GK, K, D, E, W> From<DirectedGraph<A, GK, K, D, E, W>>
-//     for DirectedGraph<B, GK, K, D, E, W>
-// where
-//     A: StorageConvert<B> + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
-//     B: StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
-//     GK: GraphKindMarker,
-//     K: Clone + Eq + std::hash::Hash,
-// {
-//     fn from(src: DirectedGraph<A, GK, K, D, E, W>) -> Self {
-//         let new_storage: B = src.storage.convert();
-//         DirectedGraph::new(new_storage)
-//     }
-// }
+impl<S, GK, K, D, E, W> UndirectedGraph<S, GK, K, D, E, W>
+where
+    S: MutableStorage<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + EdgeWeights<W = W>
+        + GraphBase<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    /// Contracts edge `e` into a fresh graph, leaving `self` untouched.
+    ///
+    /// Both endpoints merge into one node that keeps the key and data of the endpoint
+    /// with the lower `NodeId`; the data of the other endpoint is discarded. Edges incident
+    /// to either endpoint are redirected to the merged node. The contracted edge is removed,
+    /// and resulting self-loops or parallel edges are dropped when the kind forbids them.
+    pub fn contract_edge(&self, e: EdgeId) -> Self {
+        let (a, b) = self.endpoints(e);
+        let mut edges = self.logical_edge_ids();
+        // `e` may be the second arc of its pair, in which case its twin is the one listed.
+        let contracted = edges
+            .iter()
+            .position(|&f| f == e)
+            .or_else(|| edges.iter().position(|&f| self.endpoints(f) == (b, a)))
+            .expect("The contracted edge belongs to the graph");
+        edges.remove(contracted);
+
+        Self::new(contract_storage::<S, GK>(
+            &self.storage,
+            (a, b),
+            &edges,
+            true,
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// The underlying simple graph as a fresh graph: self-loops are dropped and every group of
+    /// parallel edges is collapsed into the one picked by `merge`, with its metadata.
+    pub fn to_simple(&self, merge: WeightMerge) -> UndirectedGraph<S, Simple, K, D, E, W> {
+        UndirectedGraph::new(simple_storage(
+            &self.storage,
+            &self.logical_edge_ids(),
+            true,
+            merge,
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// A fresh graph with the nodes and edges of both graphs, where `strategy` decides which
+    /// nodes of `other` are identified with nodes of `self`: `union_relabel`, `union_by_key`
+    /// or `union_by_uid`.
+    pub fn union(&self, other: &Self, strategy: MergeStrategy) -> Self
+    where
+        K: FreshKey,
+        D: NodeUid,
+    {
+        match strategy {
+            MergeStrategy::Relabel => self.union_relabel(other),
+            MergeStrategy::MergeByKey => self.union_by_key(other),
+            MergeStrategy::MergeByUid => self.union_by_uid(other),
+        }
+    }
+
+    /// A fresh graph with the nodes and edges of both graphs, kept disjoint: nodes of `other`
+    /// whose key is taken get a fresh key. Edges that would become forbidden self-loops or
+    /// parallel edges are dropped, as in the other unions. Nodes of `self` come first, in order.
+    pub fn union_relabel(&self, other: &Self) -> Self
+    where
+        K: FreshKey,
+    {
+        Self::new(union_storage::<S, GK>(
+            &self.storage,
+            &other.storage,
+            add_fresh_node,
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Like `union_relabel`, but nodes with equal keys are identified and keep the data of
+    /// the first of them.
+    pub fn union_by_key(&self, other: &Self) -> Self {
+        Self::new(union_storage::<S, GK>(
+            &self.storage,
+            &other.storage,
+            |union, key, data| {
+                union
+                    .node_id(key)
+                    .unwrap_or_else(|| union.add_node(key.clone(), data.clone()))
+            },
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Like `union_relabel`, but nodes with equal `NodeUid::uid` are identified and keep the
+    /// data of the first of them. Nodes without a uid are never merged.
+    pub fn union_by_uid(&self, other: &Self) -> Self
+    where
+        K: FreshKey,
+        D: NodeUid,
+    {
+        let mut by_uid: HashMap<u64, NodeId> = HashMap::new();
+        Self::new(union_storage::<S, GK>(
+            &self.storage,
+            &other.storage,
+            |union, key, data| {
+                let Some(uid) = data.uid() else {
+                    return add_fresh_node(union, key, data);
+                };
+                *by_uid
+                    .entry(uid)
+                    .or_insert_with(|| add_fresh_node(union, key, data))
+            },
+        ))
+        .with_settings(self.edge_policy, self.max_degree)
+    }
+
+    /// Sets the weight of the edge `a`-`b`, updating both stored arcs over directed storages.
+    /// With parallel edges, every edge between `a` and `b` gets the weight. Returns an error
+    /// if the nodes are not adjacent.
+    pub fn set_edge_weight(&mut self, a: NodeId, b: NodeId, weight: Option<W>) -> Result<(), String>
+    where
+        S: MutableWeights,
+    {
+        let mut edges: Vec<EdgeId> = self
+            .storage
+            .edges_between(a, b)
+            .chain(self.storage.edges_between(b, a))
+            .collect();
+        if edges.is_empty() {
+            return Err(format!("No edge between nodes {} and {}", a.0, b.0));
+        }
+        edges.sort_unstable();
+        edges.dedup();
+        for e in edges {
+            self.storage.set_weight(e, weight);
+        }
+        Ok(())
+    }
+}
+
+/// Plain-text listing of a graph: its nodes on one line, then one edge per line with its
+/// weight in parentheses, if any. `connector` separates the endpoints, e.g. `->` or `--`.
+/// Nodes and edges are sorted by key, so the listing does not depend on insertion order.
+fn fmt_edge_list<G>(graph: &G, connector: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    G: Graph + EdgeWeights,
+    G::Key: Display + Ord,
+    G::W: WeightLabel,
+{
+    let mut keys: Vec<&G::Key> = graph.node_ids().map(|id| graph.node_key(id)).collect();
+    keys.sort();
+    let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+    writeln!(f, "Nodes: {}", keys.join(", "))?;
+    writeln!(f, "Edges:")?;
+
+    for (e, from, to) in graph.logical_edges_sorted() {
+        write!(
+            f,
+            "  {} {} {}",
+            graph.node_key(from),
+            connector,
+            graph.node_key(to)
+        )?;
+        if let Some(label) = graph.weight_of(e).and_then(|w| w.weight_label()) {
+            write!(f, " ({})", label)?;
+        }
+        writeln!(f)?;
+    }
+
+    Ok(())
+}
+
+impl<S, GK, K, D, E, W> Display for DirectedGraph<S, GK, K, D, E, W>
+where
+    S: EdgeWeights<W = W>
+        + GraphBase<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash + Display + Ord,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd + WeightLabel,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_edge_list(self, "->", f)
+    }
+}
+
+impl<S, GK, K, D, E, W> Display for UndirectedGraph<S, GK, K, D, E, W>
+where
+    S: EdgeWeights<W = W>
+        + GraphBase<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash + Display + Ord,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd + WeightLabel,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_edge_list(self, "--", f)
+    }
+}
+
+/// One edge in the compact `Debug` output of the wrappers.
+struct DebugEdge<'a, K, W> {
+    from: &'a K,
+    to: &'a K,
+    weight: Option<W>,
+    connector: &'static str,
+}
+
+impl<K: Debug, W: WeightLabel> Debug for DebugEdge<'_, K, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {} {:?}", self.from, self.connector, self.to)?;
+        match self.weight.as_ref().and_then(|w| w.weight_label()) {
+            Some(label) => write!(f, " ({})", label),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes order, size and the logical edges by key, leaving out node data and edge metadata.
+/// Edges are sorted by key, as in the `Display` listing.
+fn fmt_debug<G>(
+    graph: &G,
+    name: &str,
+    connector: &'static str,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    G: Graph + EdgeWeights,
+    G::Key: Ord,
+    G::W: WeightLabel,
+{
+    let edges: Vec<_> = graph
+        .logical_edges_sorted()
+        .into_iter()
+        .map(|(e, from, to)| DebugEdge {
+            from: graph.node_key(from),
+            to: graph.node_key(to),
+            weight: graph.weight_of(e),
+            connector,
+        })
+        .collect();
+
+    f.debug_struct(name)
+        .field("order", &graph.order())
+        .field("size", &edges.len())
+        .field("edges", &edges)
+        .finish()
+}
+
+impl<S, GK, K, D, E, W> Debug for DirectedGraph<S, GK, K, D, E, W>
+where
+    S: EdgeWeights<W = W>
+        + GraphBase<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash + Ord,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd + WeightLabel,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_debug(self, "DirectedGraph", "->", f)
+    }
+}
+
+impl<S, GK, K, D, E, W> Debug for UndirectedGraph<S, GK, K, D, E, W>
+where
+    S: EdgeWeights<W = W>
+        + GraphBase<Key = K, Data = D, EdgeMeta = E, Weight = W>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash + Ord,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd + WeightLabel,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_debug(self, "UndirectedGraph", "--", f)
+    }
+}
+
+// A blanket `From<DirectedGraph<A>> for DirectedGraph<B>` would overlap with the reflexive
+// `From<T> for T` when `A = B`, so storage changes go through `map_storage` and `into_storage`.
+
+/// Storage snapshots of a wrapper, e.g. `AdjacencyMatrix::from(&graph)`. Keys, data, edge
+/// metadata and weights are copied; undirected graphs over directed storages give both arcs.
+impl<S, GK, K, D, E, W> From<&DirectedGraph<S, GK, K, D, E, W>> for AdjacencyMatrix<K, D, E, W>
+where
+    S: StorageConvert<AdjacencyMatrix<K, D, E, W>>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn from(graph: &DirectedGraph<S, GK, K, D, E, W>) -> Self {
+        graph.storage.convert()
+    }
+}
+
+impl<S, GK, K, D, E, W> From<&UndirectedGraph<S, GK, K, D, E, W>> for AdjacencyMatrix<K, D, E, W>
+where
+    S: StorageConvert<AdjacencyMatrix<K, D, E, W>>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn from(graph: &UndirectedGraph<S, GK, K, D, E, W>) -> Self {
+        graph.storage.convert()
+    }
+}
+
+impl<S, GK, K, D, E, W> From<&DirectedGraph<S, GK, K, D, E, W>> for AdjacencyList<K, D, E, W>
+where
+    S: StorageConvert<AdjacencyList<K, D, E, W>>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn from(graph: &DirectedGraph<S, GK, K, D, E, W>) -> Self {
+        graph.storage.convert()
+    }
+}
+
+impl<S, GK, K, D, E, W> From<&UndirectedGraph<S, GK, K, D, E, W>> for AdjacencyList<K, D, E, W>
+where
+    S: StorageConvert<AdjacencyList<K, D, E, W>>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn from(graph: &UndirectedGraph<S, GK, K, D, E, W>) -> Self {
+        graph.storage.convert()
+    }
+}
+
+impl<S, GK, K, D, E, W> From<&DirectedGraph<S, GK, K, D, E, W>> for GraphDefinition<K, D, E, W>
+where
+    S: StorageConvert<GraphDefinition<K, D, E, W>>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn from(graph: &DirectedGraph<S, GK, K, D, E, W>) -> Self {
+        graph.storage.convert()
+    }
+}
+
+impl<S, GK, K, D, E, W> From<&UndirectedGraph<S, GK, K, D, E, W>> for GraphDefinition<K, D, E, W>
+where
+    S: StorageConvert<GraphDefinition<K, D, E, W>>
+        + StorageRepresentation<Key = K, Data = D, EdgeMeta = E, Weight = W>,
+    GK: GraphKindMarker,
+    K: Debug + Clone + Eq + Hash,
+    D: Debug + Clone,
+    E: Debug + Clone,
+    W: Debug + Copy + PartialOrd,
+{
+    fn from(graph: &UndirectedGraph<S, GK, K, D, E, W>) -> Self {
+        graph.storage.convert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default)]
+    struct Tagged(Option<u64>);
+
+    impl NodeUid for Tagged {
+        fn uid(&self) -> Option<u64> {
+            self.0
+        }
+    }
+
+    type TaggedGraph = DirectedGraph<GraphDefinition<String, Tagged>, Simple, String, Tagged>;
+
+    fn graph(nodes: [(&str, u64); 2]) -> TaggedGraph {
+        let [(a, _), (b, _)] = nodes;
+        TaggedGraph::from_full(
+            nodes.map(|(key, uid)| (key.to_string(), Tagged(Some(uid)))),
+            [(a.to_string(), b.to_string(), (), None)],
+        )
+    }
+
+    fn keys_and_arcs(graph: &TaggedGraph) -> (Vec<&str>, Vec<(&str, &str)>) {
+        let keys = graph
+            .node_ids()
+            .map(|v| graph.node_key(v).as_str())
+            .collect();
+        let arcs = graph
+            .edge_ids()
+            .map(|e| {
+                let (from, to) = graph.endpoints(e);
+                (graph.node_key(from).as_str(), graph.node_key(to).as_str())
+            })
+            .collect();
+        (keys, arcs)
+    }
+
+    #[test]
+    fn union_strategies_on_overlapping_keys() {
+        // Both graphs have a node `b`; `c` shares its uid with `a`
+        let first = graph([("a", 1), ("b", 2)]);
+        let second = graph([("b", 3), ("c", 1)]);
+
+        assert_eq!(
+            keys_and_arcs(&first.union(&second, MergeStrategy::Relabel)),
+            (vec!["a", "b", "b'", "c"], vec![("a", "b"), ("b'", "c")])
+        );
+        assert_eq!(
+            keys_and_arcs(&first.union(&second, MergeStrategy::MergeByKey)),
+            (vec!["a", "b", "c"], vec![("a", "b"), ("b", "c")])
+        );
+        assert_eq!(
+            keys_and_arcs(&first.union(&second, MergeStrategy::MergeByUid)),
+            (vec!["a", "b", "b'"], vec![("a", "b"), ("b'", "a")])
+        );
+    }
+
+    #[test]
+    fn edges_change_in_place() {
+        let mut graph = UndirectedGraph::<
+            GraphDefinition<String, (), u8, i32>,
+            Pseudo,
+            String,
+            (),
+            u8,
+            i32,
+        >::new(GraphDefinition::new());
+        let (ab, ba) = graph
+            .add_edge_by_key_checked("a".into(), "b".into(), (), (), 0, Some(1))
+            .unwrap();
+        let (a, b) = graph.storage.endpoints(ab);
+
+        graph.set_edge_weight(b, a, Some(5)).unwrap();
+        assert_eq!(
+            (graph.weight_of(ab), graph.weight_of(ba)),
+            (Some(5), Some(5))
+        );
+        assert!(graph.set_edge_weight(a, a, None).is_err());
+
+        // The two arcs have independent metadata
+        *graph.storage_mut().edge_meta_mut(ab) = 7;
+        assert_eq!((graph.edge_meta(ab), graph.edge_meta(ba)), (&7, &0));
+    }
+
+    #[test]
+    fn derived_graphs_keep_settings() {
+        let mut graph = DirectedGraph::<GraphDefinition<String>, Simple, String>::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+        ]);
+        graph.set_edge_policy(EdgePolicy::KeepFirst);
+        graph.set_max_degree(Some(3));
+
+        let e = graph.edge_ids().next().unwrap();
+        let contracted = graph.contract_edge(e);
+        assert_eq!(
+            (contracted.edge_policy(), contracted.max_degree()),
+            (EdgePolicy::KeepFirst, Some(3))
+        );
+
+        let union = graph.union_by_key(&graph);
+        assert_eq!(
+            (union.edge_policy(), union.max_degree()),
+            (EdgePolicy::KeepFirst, Some(3))
+        );
+
+        let undirected: UndirectedGraph<GraphDefinition<String>, Simple, String> =
+            graph.into_undirected();
+        assert_eq!(
+            (undirected.edge_policy(), undirected.max_degree()),
+            (EdgePolicy::KeepFirst, Some(3))
+        );
+        let contracted = undirected.contract_edge(undirected.edge_ids().next().unwrap());
+        assert_eq!(
+            (contracted.edge_policy(), contracted.max_degree()),
+            (EdgePolicy::KeepFirst, Some(3))
+        );
+    }
+
+    #[test]
+    fn listings_do_not_depend_on_insertion_order() {
+        type Weighted =
+            UndirectedGraph<GraphDefinition<String, (), (), i32>, Simple, String, (), (), i32>;
+
+        let first = Weighted::from_edges([("a", "b", 1), ("b", "c", 2), ("c", "a", 3)]);
+        let second = Weighted::from_edges([("a", "c", 3), ("c", "b", 2), ("b", "a", 1)]);
+        assert_eq!(
+            first.to_string(),
+            "Nodes: a, b, c\nEdges:\n  a -- b (1)\n  a -- c (3)\n  b -- c (2)\n"
+        );
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(format!("{first:?}"), format!("{second:?}"));
+    }
+
+    #[test]
+    fn listings_label_weights_like_drawings() {
+        type Float =
+            DirectedGraph<GraphDefinition<String, (), (), f64>, Simple, String, (), (), f64>;
+
+        let graph = Float::from_edges([("a", "b", 1.0), ("b", "a", 2.5)]);
+        assert_eq!(
+            graph.to_string(),
+            "Nodes: a, b\nEdges:\n  a -> b (1)\n  b -> a (2.5)\n"
+        );
+        assert_eq!(
+            AdjacencyMatrix::from(&graph).to_string(),
+            "      a   b\n  a   .   1\n  b 2.5   .\n"
+        );
+    }
+
+    #[test]
+    fn structural_eq_ignores_insertion_order() {
+        type Weighted =
+            UndirectedGraph<GraphDefinition<String, (), (), i32>, Multi, String, (), (), i32>;
+
+        let build = |edges: [(&str, &str, i32); 3]| {
+            let mut graph = Weighted::new(GraphDefinition::new());
+            for (a, b, w) in edges {
+                graph
+                    .add_edge_by_key_checked(a.into(), b.into(), (), (), (), Some(w))
+                    .unwrap();
+            }
+            graph
+        };
+
+        let first = build([("a", "b", 1), ("b", "c", 2), ("b", "c", 2)]);
+        let second = build([("c", "b", 2), ("b", "a", 1), ("b", "c", 2)]);
+        assert!(first.structural_eq(&second));
+
+        let reweighted = build([("a", "b", 1), ("b", "c", 2), ("b", "c", 3)]);
+        assert!(!first.structural_eq(&reweighted));
+        let doubled_ab = build([("a", "b", 1), ("b", "c", 2), ("a", "b", 1)]);
+        assert!(!first.structural_eq(&doubled_ab));
+    }
+}